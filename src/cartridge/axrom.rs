@@ -0,0 +1,117 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{chr_memory, has_bus_conflicts, Mapper, PRG_ROM_START};
+use crate::cartridge::mirroring::Mirroring;
+
+const PRG_BANK_SIZE: usize = 0x8000;
+const PRG_BANK_MASK: u8 = 0b0000_0111;
+const NAMETABLE_SELECT_BIT: u8 = 0b0001_0000;
+
+/// Mapper 7: one switchable 32 KiB PRG bank and single-screen mirroring picked by
+/// bit 4 of the same register. CHR is 8 KiB of RAM.
+pub struct Axrom {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    bus_conflicts: bool,
+    prg_bank: usize,
+    mirroring: Mirroring,
+}
+
+impl Axrom {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Axrom {
+            // Only AMROM has conflicts; ANROM and AOROM do not
+            bus_conflicts: has_bus_conflicts(&cartridge, false),
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            prg_bank: 0,
+            mirroring: Mirroring::SingleScreenLower,
+        }
+    }
+}
+
+impl Mapper for Axrom {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        if address < PRG_ROM_START {
+            return;
+        }
+        let value = match self.bus_conflicts {
+            true => value & self.cpu_peek(address).unwrap_or(0xFF),
+            false => value,
+        };
+        self.prg_bank = (value & PRG_BANK_MASK) as usize;
+        self.mirroring = match value & NAMETABLE_SELECT_BIT {
+            0 => Mirroring::SingleScreenLower,
+            _ => Mirroring::SingleScreenUpper,
+        };
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                let offset = (address - PRG_ROM_START) as usize;
+                let index = (self.prg_bank * PRG_BANK_SIZE + offset) % self.prg_rom.len();
+                Some(self.prg_rom[index])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[address as usize % self.chr.len()]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let len = self.chr.len();
+            self.chr[address as usize % len] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    #[test]
+    fn test_axrom_switches_32k_bank_and_nametable() {
+        let mut axrom = Axrom::new(cartridge(
+            7,
+            0,
+            numbered_banks(8, PRG_BANK_SIZE),
+            Vec::new(),
+            0,
+        ));
+        assert_eq!(axrom.mirroring(), Mirroring::SingleScreenLower);
+
+        axrom.cpu_write(0x8000, NAMETABLE_SELECT_BIT | 3);
+
+        assert_eq!(axrom.cpu_read(0x8000), 3);
+        assert_eq!(axrom.cpu_read(0xFFFF), 3);
+        assert_eq!(axrom.mirroring(), Mirroring::SingleScreenUpper);
+    }
+
+    #[test]
+    fn test_axrom_bus_conflicts_on_amrom_submapper() {
+        let mut axrom = Axrom::new(cartridge(
+            7,
+            2,
+            numbered_banks(8, PRG_BANK_SIZE),
+            Vec::new(),
+            0,
+        ));
+
+        // Bank 0 is all zeros, so the ROM pulls every bit low
+        axrom.cpu_write(0x8000, NAMETABLE_SELECT_BIT | 3);
+
+        assert_eq!(axrom.cpu_read(0x8000), 0);
+        assert_eq!(axrom.mirroring(), Mirroring::SingleScreenLower);
+    }
+}
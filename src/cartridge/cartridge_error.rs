@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors raised while loading a ROM image or its save file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CartridgeError {
+    InvalidMagic,
+    InvalidHeader(&'static str),
+    Truncated {
+        section: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    UnsupportedMapper(u16),
+    Io(String),
+}
+
+impl fmt::Display for CartridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CartridgeError::InvalidMagic => write!(f, "file is not an iNES image"),
+            CartridgeError::InvalidHeader(reason) => write!(f, "invalid header: {}", reason),
+            CartridgeError::Truncated {
+                section,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} is truncated: expected {} bytes, found {}",
+                section, expected, actual
+            ),
+            CartridgeError::UnsupportedMapper(mapper) => {
+                write!(f, "mapper {} is not supported", mapper)
+            }
+            CartridgeError::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl Error for CartridgeError {}
@@ -0,0 +1,156 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::header::{Header, HEADER_SIZE, TRAINER_SIZE};
+use crate::cartridge::save_file::SaveFile;
+use std::fs;
+use std::path::Path;
+
+/// A parsed ROM image: the header plus the raw trainer, PRG-ROM and CHR-ROM data,
+/// and the board's PRG-RAM.
+///
+/// An empty `chr_rom` means the board uses CHR-RAM instead. `prg_ram` covers both
+/// work RAM and battery-backed RAM and starts zeroed unless a save was loaded.
+/// Battery-backed boards loaded from a file also carry the `save_file` to flush
+/// that RAM back to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cartridge {
+    pub header: Header,
+    pub trainer: Option<Vec<u8>>,
+    pub prg_rom: Vec<u8>,
+    pub chr_rom: Vec<u8>,
+    pub prg_ram: Vec<u8>,
+    pub save_file: Option<SaveFile>,
+}
+
+// Splits `size` bytes off the front of `bytes`, failing if there are not enough left
+fn take<'a>(
+    bytes: &mut &'a [u8],
+    size: usize,
+    section: &'static str,
+) -> Result<&'a [u8], CartridgeError> {
+    if bytes.len() < size {
+        return Err(CartridgeError::Truncated {
+            section,
+            expected: size,
+            actual: bytes.len(),
+        });
+    }
+    let (section_bytes, rest) = bytes.split_at(size);
+    *bytes = rest;
+    Ok(section_bytes)
+}
+
+impl Cartridge {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CartridgeError> {
+        let header = Header::parse(bytes)?;
+        let mut rest = &bytes[HEADER_SIZE..];
+
+        let trainer = match header.trainer {
+            true => Some(take(&mut rest, TRAINER_SIZE, "trainer")?.to_vec()),
+            false => None,
+        };
+        let prg_rom = take(&mut rest, header.prg_rom_size, "PRG-ROM")?.to_vec();
+        let chr_rom = take(&mut rest, header.chr_rom_size, "CHR-ROM")?.to_vec();
+        let prg_ram = vec![0; header.prg_ram_size + header.prg_nvram_size];
+
+        Ok(Cartridge {
+            header,
+            trainer,
+            prg_rom,
+            chr_rom,
+            prg_ram,
+            save_file: None,
+        })
+    }
+
+    /// Loads a ROM and, for battery-backed boards, the `.sav` file next to it.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, CartridgeError> {
+        let bytes = fs::read(&path).map_err(|error| CartridgeError::Io(error.to_string()))?;
+        let mut cartridge = Self::from_bytes(&bytes)?;
+        if cartridge.header.battery {
+            let save_file = SaveFile::for_rom(&path);
+            save_file.load(&mut cartridge.prg_ram)?;
+            cartridge.save_file = Some(save_file);
+        }
+        Ok(cartridge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_RAM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::mirroring::Mirroring;
+    use crate::cartridge::test_rom::temp_path;
+
+    fn rom(prg_banks: u8, chr_banks: u8, flags_6: u8) -> Vec<u8> {
+        let mut bytes = vec![b'N', b'E', b'S', 0x1a, prg_banks, chr_banks, flags_6];
+        bytes.resize(HEADER_SIZE, 0);
+        if flags_6 & 0b0000_0100 != 0 {
+            bytes.extend(vec![0xEE; TRAINER_SIZE]);
+        }
+        bytes.extend(vec![0xAA; prg_banks as usize * PRG_ROM_BANK_SIZE]);
+        bytes.extend(vec![0xCC; chr_banks as usize * CHR_ROM_BANK_SIZE]);
+        bytes
+    }
+
+    #[test]
+    fn test_from_bytes_splits_sections() {
+        let cartridge = Cartridge::from_bytes(&rom(2, 1, 0b0000_0001)).unwrap();
+
+        assert_eq!(cartridge.trainer, None);
+        assert_eq!(cartridge.prg_rom.len(), 2 * PRG_ROM_BANK_SIZE);
+        assert_eq!(cartridge.chr_rom.len(), CHR_ROM_BANK_SIZE);
+        assert!(cartridge.prg_rom.iter().all(|byte| *byte == 0xAA));
+        assert!(cartridge.chr_rom.iter().all(|byte| *byte == 0xCC));
+        assert_eq!(cartridge.header.mirroring, Mirroring::Vertical);
+    }
+
+    #[test]
+    fn test_from_bytes_reads_trainer_and_chr_ram_boards() {
+        let cartridge = Cartridge::from_bytes(&rom(1, 0, 0b0000_0110)).unwrap();
+
+        assert_eq!(cartridge.trainer, Some(vec![0xEE; TRAINER_SIZE]));
+        assert_eq!(cartridge.prg_rom[0], 0xAA);
+        assert!(cartridge.chr_rom.is_empty());
+        assert!(cartridge.header.battery);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_truncated_banks() {
+        let mut bytes = rom(2, 1, 0);
+        bytes.truncate(HEADER_SIZE + 2 * PRG_ROM_BANK_SIZE + 100);
+
+        assert_eq!(
+            Cartridge::from_bytes(&bytes),
+            Err(CartridgeError::Truncated {
+                section: "CHR-ROM",
+                expected: CHR_ROM_BANK_SIZE,
+                actual: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_file_loads_battery_save() {
+        let rom_path = temp_path("battery.nes");
+        let save_file = SaveFile::for_rom(&rom_path);
+        fs::write(&rom_path, rom(1, 1, 0b0000_0010)).unwrap();
+        save_file.flush(&[0x5A; 16]).unwrap();
+
+        let cartridge = Cartridge::from_file(&rom_path).unwrap();
+        fs::remove_file(save_file.path()).unwrap();
+        fs::remove_file(&rom_path).unwrap();
+
+        assert_eq!(cartridge.save_file, Some(save_file));
+        assert_eq!(cartridge.prg_ram.len(), PRG_RAM_BANK_SIZE);
+        assert_eq!(cartridge.prg_ram[..16], [0x5A; 16]);
+        assert_eq!(cartridge.prg_ram[16], 0);
+    }
+
+    #[test]
+    fn test_from_file_reports_missing_file() {
+        let result = Cartridge::from_file("does/not/exist.nes");
+
+        assert!(matches!(result, Err(CartridgeError::Io(_))));
+    }
+}
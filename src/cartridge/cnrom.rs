@@ -0,0 +1,130 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, has_bus_conflicts, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START,
+    PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const CHR_BANK_SIZE: usize = 0x2000;
+
+/// Mapper 3: fixed PRG laid out like NROM and a switchable 8 KiB CHR bank.
+pub struct Cnrom {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    mirroring: Mirroring,
+    bus_conflicts: bool,
+    chr_bank: usize,
+}
+
+impl Cnrom {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Cnrom {
+            bus_conflicts: has_bus_conflicts(&cartridge, true),
+            prg_ram: cartridge.prg_ram,
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            chr_bank: 0,
+        }
+    }
+
+    fn chr_index(&self, address: u16) -> usize {
+        let offset = address as usize % CHR_BANK_SIZE;
+        (self.chr_bank * CHR_BANK_SIZE + offset) % self.chr.len()
+    }
+}
+
+impl Mapper for Cnrom {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => write_prg_ram(&mut self.prg_ram, address, value),
+            PRG_ROM_START..=0xFFFF => {
+                let value = match self.bus_conflicts {
+                    true => value & self.cpu_peek(address).unwrap_or(0xFF),
+                    false => value,
+                };
+                self.chr_bank = value as usize;
+            }
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => read_prg_ram(&self.prg_ram, address),
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                let index = (address - PRG_ROM_START) as usize % self.prg_rom.len();
+                Some(self.prg_rom[index])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[self.chr_index(address)]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let index = self.chr_index(address);
+            self.chr[index] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    #[test]
+    fn test_cnrom_switches_chr_bank() {
+        let mut cnrom = Cnrom::new(cartridge(
+            3,
+            1,
+            numbered_banks(2, PRG_ROM_BANK_SIZE),
+            numbered_banks(4, CHR_ROM_BANK_SIZE),
+            0,
+        ));
+        assert_eq!(cnrom.ppu_read(0x1FFF), 0);
+
+        cnrom.cpu_write(0x8000, 3);
+
+        assert_eq!(cnrom.ppu_read(0x0000), 3);
+        assert_eq!(cnrom.cpu_read(0xC000), 1);
+    }
+
+    #[test]
+    fn test_cnrom_bus_conflicts_by_default() {
+        let mut prg_rom = vec![0xFF; 2 * PRG_ROM_BANK_SIZE];
+        prg_rom[0x10] = 0x01;
+        let mut cnrom = Cnrom::new(cartridge(
+            3,
+            0,
+            prg_rom,
+            numbered_banks(4, CHR_ROM_BANK_SIZE),
+            0,
+        ));
+
+        cnrom.cpu_write(0x8010, 0x03);
+
+        assert_eq!(cnrom.ppu_read(0x0000), 1);
+    }
+}
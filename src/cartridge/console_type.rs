@@ -0,0 +1,8 @@
+/// The console the image targets (NES 2.0 byte 7, extended by byte 13).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleType {
+    Nes,
+    VsSystem { ppu_type: u8, hardware_type: u8 },
+    Playchoice10,
+    Extended(u8),
+}
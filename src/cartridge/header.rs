@@ -0,0 +1,308 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::console_type::ConsoleType;
+use crate::cartridge::header_format::HeaderFormat;
+use crate::cartridge::mirroring::Mirroring;
+use crate::cartridge::timing_region::TimingRegion;
+
+pub const HEADER_SIZE: usize = 16;
+pub const TRAINER_SIZE: usize = 512;
+pub const PRG_ROM_BANK_SIZE: usize = 0x4000;
+pub const CHR_ROM_BANK_SIZE: usize = 0x2000;
+pub const PRG_RAM_BANK_SIZE: usize = 0x2000;
+pub const CHR_RAM_SIZE: usize = 0x2000;
+const MAGIC: &[u8; 4] = b"NES\x1a";
+
+const FLAGS_6_VERTICAL_MIRRORING: u8 = 0b0000_0001;
+const FLAGS_6_BATTERY: u8 = 0b0000_0010;
+const FLAGS_6_TRAINER: u8 = 0b0000_0100;
+const FLAGS_6_FOUR_SCREEN: u8 = 0b0000_1000;
+const FLAGS_7_CONSOLE_TYPE: u8 = 0b0000_0011;
+const FLAGS_7_NES2_MASK: u8 = 0b0000_1100;
+const FLAGS_7_NES2_IDENTIFIER: u8 = 0b0000_1000;
+
+// An MSB nibble of $F switches a ROM size to the exponent-multiplier form
+const EXPONENT_MULTIPLIER_NIBBLE: u8 = 0x0F;
+// RAM sizes are stored as shift counts: 64 << n bytes, or nothing when n is 0
+const RAM_SIZE_SHIFT_BASE: usize = 64;
+
+/// The 16-byte iNES header at the start of every .nes file.
+///
+/// NES 2.0 fields fall back to their iNES 1.0 meaning when the identifier bits
+/// are not set: PRG-RAM comes from byte 8 and counts as NVRAM when the battery
+/// flag is set, and boards without CHR-ROM get 8 KiB of CHR-RAM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub format: HeaderFormat,
+    pub prg_rom_size: usize,
+    pub chr_rom_size: usize,
+    pub mapper: u16,
+    pub submapper: u8,
+    pub mirroring: Mirroring,
+    pub battery: bool,
+    pub trainer: bool,
+    pub prg_ram_size: usize,
+    pub prg_nvram_size: usize,
+    pub chr_ram_size: usize,
+    pub chr_nvram_size: usize,
+    pub timing_region: TimingRegion,
+    pub console_type: ConsoleType,
+    pub default_expansion_device: u8,
+}
+
+fn rom_size(lsb: u8, msb_nibble: u8, unit: usize) -> Result<usize, CartridgeError> {
+    if msb_nibble != EXPONENT_MULTIPLIER_NIBBLE {
+        return Ok((((msb_nibble as usize) << 8) | lsb as usize) * unit);
+    }
+    let exponent = (lsb >> 2) as u32;
+    let multiplier = (lsb & 0b0000_0011) as usize * 2 + 1;
+    1usize
+        .checked_shl(exponent)
+        .and_then(|size| size.checked_mul(multiplier))
+        .ok_or(CartridgeError::InvalidHeader(
+            "ROM size does not fit in memory",
+        ))
+}
+
+fn ram_size(shift: u8) -> usize {
+    match shift {
+        0 => 0,
+        shift => RAM_SIZE_SHIFT_BASE << shift,
+    }
+}
+
+impl Header {
+    pub fn parse(bytes: &[u8]) -> Result<Self, CartridgeError> {
+        if bytes.len() < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(CartridgeError::InvalidMagic);
+        }
+        if bytes.len() < HEADER_SIZE {
+            return Err(CartridgeError::Truncated {
+                section: "header",
+                expected: HEADER_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        let flags_6 = bytes[6];
+        let mirroring = if flags_6 & FLAGS_6_FOUR_SCREEN != 0 {
+            Mirroring::FourScreen
+        } else if flags_6 & FLAGS_6_VERTICAL_MIRRORING != 0 {
+            Mirroring::Vertical
+        } else {
+            Mirroring::Horizontal
+        };
+        let battery = flags_6 & FLAGS_6_BATTERY != 0;
+        let trainer = flags_6 & FLAGS_6_TRAINER != 0;
+
+        if bytes[7] & FLAGS_7_NES2_MASK == FLAGS_7_NES2_IDENTIFIER {
+            return Self::parse_nes2(bytes, mirroring, battery, trainer);
+        }
+
+        // Old dumping tools wrote a signature over bytes 7-15, so byte 7 is only
+        // trusted when the padding at 12-15 is clean
+        let flags_7 = match bytes[12..16].iter().all(|byte| *byte == 0) {
+            true => bytes[7],
+            false => 0,
+        };
+        let chr_rom_size = bytes[5] as usize * CHR_ROM_BANK_SIZE;
+        // A zero here means one bank, for compatibility with older images
+        let prg_ram_size = bytes[8].max(1) as usize * PRG_RAM_BANK_SIZE;
+
+        Ok(Header {
+            format: HeaderFormat::Ines,
+            prg_rom_size: bytes[4] as usize * PRG_ROM_BANK_SIZE,
+            chr_rom_size,
+            mapper: ((flags_7 & 0xF0) | (flags_6 >> 4)) as u16,
+            submapper: 0,
+            mirroring,
+            battery,
+            trainer,
+            prg_ram_size: if battery { 0 } else { prg_ram_size },
+            prg_nvram_size: if battery { prg_ram_size } else { 0 },
+            chr_ram_size: if chr_rom_size == 0 { CHR_RAM_SIZE } else { 0 },
+            chr_nvram_size: 0,
+            timing_region: TimingRegion::Ntsc,
+            console_type: match flags_7 & FLAGS_7_CONSOLE_TYPE {
+                0b01 => ConsoleType::VsSystem {
+                    ppu_type: 0,
+                    hardware_type: 0,
+                },
+                0b10 => ConsoleType::Playchoice10,
+                _ => ConsoleType::Nes,
+            },
+            default_expansion_device: 0,
+        })
+    }
+
+    fn parse_nes2(
+        bytes: &[u8],
+        mirroring: Mirroring,
+        battery: bool,
+        trainer: bool,
+    ) -> Result<Self, CartridgeError> {
+        let (flags_6, flags_7, mapper_msb) = (bytes[6], bytes[7], bytes[8]);
+
+        Ok(Header {
+            format: HeaderFormat::Nes2,
+            prg_rom_size: rom_size(bytes[4], bytes[9] & 0x0F, PRG_ROM_BANK_SIZE)?,
+            chr_rom_size: rom_size(bytes[5], bytes[9] >> 4, CHR_ROM_BANK_SIZE)?,
+            mapper: ((mapper_msb & 0x0F) as u16) << 8
+                | (flags_7 & 0xF0) as u16
+                | (flags_6 >> 4) as u16,
+            submapper: mapper_msb >> 4,
+            mirroring,
+            battery,
+            trainer,
+            prg_ram_size: ram_size(bytes[10] & 0x0F),
+            prg_nvram_size: ram_size(bytes[10] >> 4),
+            chr_ram_size: ram_size(bytes[11] & 0x0F),
+            chr_nvram_size: ram_size(bytes[11] >> 4),
+            timing_region: match bytes[12] & 0b0000_0011 {
+                0 => TimingRegion::Ntsc,
+                1 => TimingRegion::Pal,
+                2 => TimingRegion::MultipleRegion,
+                _ => TimingRegion::Dendy,
+            },
+            console_type: match flags_7 & FLAGS_7_CONSOLE_TYPE {
+                0 => ConsoleType::Nes,
+                1 => ConsoleType::VsSystem {
+                    ppu_type: bytes[13] & 0x0F,
+                    hardware_type: bytes[13] >> 4,
+                },
+                2 => ConsoleType::Playchoice10,
+                _ => ConsoleType::Extended(bytes[13] & 0x0F),
+            },
+            default_expansion_device: bytes[15] & 0b0011_1111,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(prg_banks: u8, chr_banks: u8, flags_6: u8, flags_7: u8) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0; HEADER_SIZE];
+        bytes[..4].copy_from_slice(MAGIC);
+        bytes[4] = prg_banks;
+        bytes[5] = chr_banks;
+        bytes[6] = flags_6;
+        bytes[7] = flags_7;
+        bytes
+    }
+
+    #[test]
+    fn test_parse_sizes_flags_and_mapper() {
+        let header = Header::parse(&header(2, 1, 0b0001_0011, 0b0100_0000)).unwrap();
+
+        assert_eq!(header.format, HeaderFormat::Ines);
+        assert_eq!(header.prg_rom_size, 0x8000);
+        assert_eq!(header.chr_rom_size, 0x2000);
+        assert_eq!(header.mapper, 0x41);
+        assert_eq!(header.mirroring, Mirroring::Vertical);
+        assert!(header.battery);
+        assert!(!header.trainer);
+        assert_eq!(header.prg_ram_size, 0);
+        assert_eq!(header.prg_nvram_size, PRG_RAM_BANK_SIZE);
+        assert_eq!(header.chr_ram_size, 0);
+    }
+
+    #[test]
+    fn test_parse_mirroring_modes() {
+        let horizontal = Header::parse(&header(1, 1, 0b0000_0000, 0)).unwrap();
+        let four_screen = Header::parse(&header(1, 1, 0b0000_1001, 0)).unwrap();
+
+        assert_eq!(horizontal.mirroring, Mirroring::Horizontal);
+        assert_eq!(four_screen.mirroring, Mirroring::FourScreen);
+    }
+
+    #[test]
+    fn test_parse_ignores_byte_7_when_padding_is_dirty() {
+        let mut bytes = header(1, 1, 0b0010_0000, 0b0100_0100);
+        bytes[7..16].copy_from_slice(b"DiskDude!");
+
+        assert_eq!(Header::parse(&bytes).unwrap().mapper, 0x02);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_magic_and_short_header() {
+        assert_eq!(
+            Header::parse(b"NES\x00 and more bytes"),
+            Err(CartridgeError::InvalidMagic)
+        );
+        assert_eq!(
+            Header::parse(b"NES\x1a\x01"),
+            Err(CartridgeError::Truncated {
+                section: "header",
+                expected: HEADER_SIZE,
+                actual: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ines_defaults_for_nes2_fields() {
+        let header = Header::parse(&header(1, 0, 0, 0b0000_0001)).unwrap();
+
+        assert_eq!(header.submapper, 0);
+        assert_eq!(header.prg_ram_size, PRG_RAM_BANK_SIZE);
+        assert_eq!(header.chr_ram_size, CHR_RAM_SIZE);
+        assert_eq!(header.timing_region, TimingRegion::Ntsc);
+        assert_eq!(
+            header.console_type,
+            ConsoleType::VsSystem {
+                ppu_type: 0,
+                hardware_type: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_nes2_fields() {
+        let mut bytes = header(0x02, 0x01, 0b0100_0010, 0b0101_1000);
+        bytes[8] = 0b0011_0001; // submapper 3, mapper bits 8-11 = 1
+        bytes[9] = 0x12; // PRG MSB 2, CHR MSB 1
+        bytes[10] = 0x70; // 8 KiB PRG-NVRAM
+        bytes[11] = 0x07; // 8 KiB CHR-RAM
+        bytes[12] = 0x01;
+        bytes[15] = 0x01;
+
+        let header = Header::parse(&bytes).unwrap();
+
+        assert_eq!(header.format, HeaderFormat::Nes2);
+        assert_eq!(header.mapper, 0x154);
+        assert_eq!(header.submapper, 3);
+        assert_eq!(header.prg_rom_size, 0x202 * PRG_ROM_BANK_SIZE);
+        assert_eq!(header.chr_rom_size, 0x101 * CHR_ROM_BANK_SIZE);
+        assert_eq!(header.prg_ram_size, 0);
+        assert_eq!(header.prg_nvram_size, 0x2000);
+        assert_eq!(header.chr_ram_size, 0x2000);
+        assert_eq!(header.chr_nvram_size, 0);
+        assert_eq!(header.timing_region, TimingRegion::Pal);
+        assert_eq!(header.console_type, ConsoleType::Nes);
+        assert_eq!(header.default_expansion_device, 0x01);
+    }
+
+    #[test]
+    fn test_parse_nes2_exponent_multiplier_sizes() {
+        let mut bytes = header(0b0000_1001, 0b1111_1111, 0, 0b0000_1011);
+        bytes[9] = 0xFF;
+        bytes[13] = 0x05;
+
+        let result = Header::parse(&bytes);
+        assert_eq!(
+            result,
+            Err(CartridgeError::InvalidHeader(
+                "ROM size does not fit in memory"
+            ))
+        );
+
+        bytes[5] = 0b0000_1000;
+        let header = Header::parse(&bytes).unwrap();
+
+        // 2^2 * 3 and 2^2 * 1
+        assert_eq!(header.prg_rom_size, 12);
+        assert_eq!(header.chr_rom_size, 4);
+        assert_eq!(header.console_type, ConsoleType::Extended(0x05));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::cartridge::axrom::Axrom;
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::cnrom::Cnrom;
+use crate::cartridge::header::CHR_RAM_SIZE;
+use crate::cartridge::mirroring::Mirroring;
+use crate::cartridge::mmc1::Mmc1;
+use crate::cartridge::mmc2::Mmc2;
+use crate::cartridge::mmc3::Mmc3;
+use crate::cartridge::nrom::Nrom;
+use crate::cartridge::uxrom::Uxrom;
+use crate::cartridge::vrc4::Vrc4;
+
+pub(crate) const PRG_RAM_START: u16 = 0x6000;
+pub(crate) const PRG_RAM_END: u16 = 0x7FFF;
+pub(crate) const PRG_ROM_START: u16 = 0x8000;
+
+/// The board logic of a cartridge: bank switching, on-board RAM and IRQ counters.
+///
+/// The CPU side covers $4020-$FFFF and the PPU side the pattern tables at
+/// $0000-$1FFF.
+pub trait Mapper {
+    /// Reads with side effects. Boards whose reads have none just peek, with open
+    /// bus read as 0.
+    fn cpu_read(&mut self, address: u16) -> u8 {
+        self.cpu_peek(address).unwrap_or(0)
+    }
+
+    fn cpu_write(&mut self, address: u16, value: u8);
+
+    /// The first of a read-modify-write instruction's two writes; see
+    /// `Bus::dummy_write`.
+    fn cpu_dummy_write(&mut self, address: u16, value: u8) {
+        self.cpu_write(address, value);
+    }
+
+    /// Reads without side effects; see `Bus::peek`.
+    fn cpu_peek(&self, _address: u16) -> Option<u8> {
+        None
+    }
+
+    /// Advances board timers by the CPU cycles of the last instruction.
+    fn cpu_clock(&mut self, _cycles: u8) {}
+
+    fn ppu_read(&mut self, address: u16) -> u8;
+
+    fn ppu_write(&mut self, address: u16, value: u8);
+
+    /// Called with the address of every PPU memory access, nametables included,
+    /// for boards that watch the PPU address bus.
+    fn ppu_address(&mut self, _address: u16) {}
+
+    fn mirroring(&self) -> Mirroring;
+
+    /// The work RAM at $6000-$7FFF, so hosts can persist battery saves.
+    fn prg_ram(&self) -> &[u8] {
+        &[]
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut []
+    }
+
+    /// Restores PRG-RAM from a save, copying as much as fits when the sizes differ.
+    fn load_prg_ram(&mut self, save: &[u8]) {
+        let prg_ram = self.prg_ram_mut();
+        let len = save.len().min(prg_ram.len());
+        prg_ram[..len].copy_from_slice(&save[..len]);
+    }
+
+    /// Whether the board is currently pulling the CPU's IRQ line low.
+    fn irq(&self) -> bool {
+        false
+    }
+}
+
+pub fn create_mapper(cartridge: Cartridge) -> Result<Box<dyn Mapper>, CartridgeError> {
+    match cartridge.header.mapper {
+        0 => Ok(Box::new(Nrom::new(cartridge))),
+        1 => Ok(Box::new(Mmc1::new(cartridge))),
+        2 => Ok(Box::new(Uxrom::new(cartridge))),
+        3 => Ok(Box::new(Cnrom::new(cartridge))),
+        4 => Ok(Box::new(Mmc3::new(cartridge))),
+        7 => Ok(Box::new(Axrom::new(cartridge))),
+        9 | 10 => Ok(Box::new(Mmc2::new(cartridge))),
+        21 | 22 | 23 | 25 => Ok(Box::new(Vrc4::new(cartridge))),
+        mapper => Err(CartridgeError::UnsupportedMapper(mapper)),
+    }
+}
+
+// Pattern table memory for a board: its CHR-ROM, or CHR-RAM when it has none.
+// The flag tells whether PPU writes should stick.
+pub(crate) fn chr_memory(cartridge: &Cartridge) -> (Vec<u8>, bool) {
+    match cartridge.chr_rom.is_empty() {
+        true => {
+            let size =
+                (cartridge.header.chr_ram_size + cartridge.header.chr_nvram_size).max(CHR_RAM_SIZE);
+            (vec![0; size], true)
+        }
+        false => (cartridge.chr_rom.clone(), false),
+    }
+}
+
+// On discrete-logic boards submapper 1 means no bus conflicts and 2 means the ROM
+// ANDs its byte into register writes; 0 keeps the board's usual wiring
+pub(crate) fn has_bus_conflicts(cartridge: &Cartridge, default: bool) -> bool {
+    match cartridge.header.submapper {
+        1 => false,
+        2 => true,
+        _ => default,
+    }
+}
+
+// $6000-$7FFF on boards with work RAM, mirrored when there is less than 8 KiB.
+// Without any the range stays open bus.
+pub(crate) fn read_prg_ram(prg_ram: &[u8], address: u16) -> Option<u8> {
+    match prg_ram.is_empty() {
+        true => None,
+        false => Some(prg_ram[(address - PRG_RAM_START) as usize % prg_ram.len()]),
+    }
+}
+
+pub(crate) fn write_prg_ram(prg_ram: &mut [u8], address: u16, value: u8) {
+    if !prg_ram.is_empty() {
+        let len = prg_ram.len();
+        prg_ram[(address - PRG_RAM_START) as usize % len] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    #[test]
+    fn test_create_mapper_by_number() {
+        let prg_rom = numbered_banks(2, PRG_ROM_BANK_SIZE);
+        let chr_rom = numbered_banks(1, CHR_ROM_BANK_SIZE);
+
+        let mut nrom = create_mapper(cartridge(0, 0, prg_rom.clone(), chr_rom.clone(), 0)).unwrap();
+        assert_eq!(nrom.cpu_read(0xC000), 1);
+
+        let axrom = create_mapper(cartridge(7, 0, prg_rom.clone(), chr_rom.clone(), 0)).unwrap();
+        assert_eq!(axrom.mirroring(), Mirroring::SingleScreenLower);
+
+        let unsupported = create_mapper(cartridge(0x155, 0, prg_rom, chr_rom, 0));
+        assert!(matches!(
+            unsupported,
+            Err(CartridgeError::UnsupportedMapper(0x155))
+        ));
+    }
+
+    #[test]
+    fn test_prg_ram_can_be_saved_and_restored() {
+        let prg_rom = numbered_banks(2, PRG_ROM_BANK_SIZE);
+        let battery = 0b0000_0010;
+        let mut mapper =
+            create_mapper(cartridge(1, 0, prg_rom.clone(), Vec::new(), battery)).unwrap();
+        mapper.cpu_write(0x6001, 0x77);
+        let save = mapper.prg_ram().to_vec();
+        assert_eq!(save[1], 0x77);
+
+        let mut restored = create_mapper(cartridge(1, 0, prg_rom, Vec::new(), battery)).unwrap();
+        restored.load_prg_ram(&save);
+
+        assert_eq!(restored.cpu_read(0x6001), 0x77);
+    }
+
+    #[test]
+    fn test_load_prg_ram_copies_what_fits() {
+        let prg_rom = numbered_banks(2, PRG_ROM_BANK_SIZE);
+        let mut mapper = create_mapper(cartridge(0, 0, prg_rom, Vec::new(), 0)).unwrap();
+        let prg_ram_size = mapper.prg_ram().len();
+
+        mapper.load_prg_ram(&[0x11; 4]);
+        assert_eq!(mapper.prg_ram()[..5], [0x11, 0x11, 0x11, 0x11, 0x00]);
+
+        mapper.load_prg_ram(&vec![0x22; prg_ram_size + 16]);
+        assert!(mapper.prg_ram().iter().all(|byte| *byte == 0x22));
+    }
+}
@@ -0,0 +1,9 @@
+/// How the PPU's two physical nametables are arranged in its four logical slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mirroring {
+    Horizontal,
+    Vertical,
+    FourScreen,
+    SingleScreenLower,
+    SingleScreenUpper,
+}
@@ -0,0 +1,347 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START, PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const PRG_SWITCH_BOUNDARY: u16 = 0xC000;
+const PRG_BANK_SIZE: usize = 0x4000;
+const CHR_BANK_SIZE: usize = 0x1000;
+// SUROM and friends use a CHR register bit to pick the 256 KiB PRG half
+const PRG_OUTER_BANK_SIZE: usize = 0x40000;
+const PRG_OUTER_BANK_BIT: u8 = 0b1_0000;
+
+const SHIFT_RESET_BIT: u8 = 0b1000_0000;
+const SHIFT_REGISTER_WRITES: u8 = 5;
+const CONTROL_PRG_MODE_FIX_LAST: u8 = 0b0_1100;
+const CONTROL_CHR_4K_MODE: u8 = 0b1_0000;
+const PRG_BANK_RAM_DISABLE: u8 = 0b1_0000;
+
+/// Mapper 1: registers are loaded one bit at a time through a 5-write serial port.
+///
+/// Writing a value with bit 7 set resets the port and switches PRG to fix-last
+/// mode. Like the real chip, a write on the cycle right after another one is
+/// ignored, so of the two writes of a read-modify-write instruction only the
+/// first, unmodified one reaches the port.
+pub struct Mmc1 {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    shift_register: u8,
+    write_count: u8,
+    control: u8,
+    chr_bank_0: u8,
+    chr_bank_1: u8,
+    prg_bank: u8,
+    // Set by the dummy write of an RMW instruction, whose second write follows
+    // on the very next cycle
+    ignore_next_write: bool,
+}
+
+impl Mmc1 {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Mmc1 {
+            prg_ram: cartridge.prg_ram,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            shift_register: 0,
+            write_count: 0,
+            control: CONTROL_PRG_MODE_FIX_LAST,
+            chr_bank_0: 0,
+            chr_bank_1: 0,
+            prg_bank: 0,
+            ignore_next_write: false,
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        match (address >> 13) & 0b11 {
+            0 => self.control = value,
+            1 => self.chr_bank_0 = value,
+            2 => self.chr_bank_1 = value,
+            _ => self.prg_bank = value,
+        }
+    }
+
+    fn prg_rom_index(&self, address: u16) -> usize {
+        let outer = match self.prg_rom.len() > PRG_OUTER_BANK_SIZE {
+            true => (self.chr_bank_0 & PRG_OUTER_BANK_BIT) as usize,
+            false => 0,
+        };
+        let bank = (self.prg_bank & 0x0F) as usize;
+        let upper_half = address >= PRG_SWITCH_BOUNDARY;
+        let selected = match (self.control >> 2) & 0b11 {
+            // 32 KiB mode ignores the low bank bit
+            0 | 1 => (bank & !1) + upper_half as usize,
+            2 => match upper_half {
+                true => bank,
+                false => 0,
+            },
+            _ => match upper_half {
+                true => 0x0F,
+                false => bank,
+            },
+        };
+        let offset = (address - PRG_ROM_START) as usize % PRG_BANK_SIZE;
+        ((outer | selected) * PRG_BANK_SIZE + offset) % self.prg_rom.len()
+    }
+
+    fn chr_index(&self, address: u16) -> usize {
+        let upper_half = address >= CHR_BANK_SIZE as u16;
+        let bank = match self.control & CONTROL_CHR_4K_MODE != 0 {
+            true if upper_half => self.chr_bank_1,
+            true => self.chr_bank_0,
+            false => (self.chr_bank_0 & !1) + upper_half as u8,
+        };
+        let offset = address as usize % CHR_BANK_SIZE;
+        (bank as usize * CHR_BANK_SIZE + offset) % self.chr.len()
+    }
+
+    fn prg_ram_enabled(&self) -> bool {
+        !self.prg_ram.is_empty() && self.prg_bank & PRG_BANK_RAM_DISABLE == 0
+    }
+}
+
+impl Mapper for Mmc1 {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        let ignored = std::mem::take(&mut self.ignore_next_write);
+        match address {
+            PRG_RAM_START..=PRG_RAM_END if self.prg_ram_enabled() => {
+                write_prg_ram(&mut self.prg_ram, address, value)
+            }
+            PRG_ROM_START..=0xFFFF if !ignored => {
+                if value & SHIFT_RESET_BIT != 0 {
+                    self.shift_register = 0;
+                    self.write_count = 0;
+                    self.control |= CONTROL_PRG_MODE_FIX_LAST;
+                    return;
+                }
+                self.shift_register |= (value & 1) << self.write_count;
+                self.write_count += 1;
+                if self.write_count == SHIFT_REGISTER_WRITES {
+                    self.write_register(address, self.shift_register);
+                    self.shift_register = 0;
+                    self.write_count = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END if self.prg_ram_enabled() => {
+                read_prg_ram(&self.prg_ram, address)
+            }
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                Some(self.prg_rom[self.prg_rom_index(address)])
+            }
+            _ => None,
+        }
+    }
+
+    fn cpu_dummy_write(&mut self, address: u16, value: u8) {
+        self.cpu_write(address, value);
+        self.ignore_next_write = true;
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[self.chr_index(address)]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let index = self.chr_index(address);
+            self.chr[index] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        match self.control & 0b11 {
+            0 => Mirroring::SingleScreenLower,
+            1 => Mirroring::SingleScreenUpper,
+            2 => Mirroring::Vertical,
+            _ => Mirroring::Horizontal,
+        }
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+    use crate::cpu::cpu_model::CPU;
+    use crate::cpu::memory_map::MemoryMap;
+
+    fn create_test_mmc1(prg_banks: usize) -> Mmc1 {
+        Mmc1::new(cartridge(
+            1,
+            0,
+            numbered_banks(prg_banks, PRG_ROM_BANK_SIZE),
+            numbered_banks(4, CHR_BANK_SIZE * 2),
+            0,
+        ))
+    }
+
+    // Shifts `value` in LSB first
+    fn load_register(mmc1: &mut Mmc1, address: u16, value: u8) {
+        for bit in 0..5 {
+            mmc1.cpu_write(address, value >> bit);
+        }
+    }
+
+    #[test]
+    fn test_power_on_fixes_last_bank() {
+        let mut mmc1 = create_test_mmc1(8);
+
+        assert_eq!(mmc1.cpu_read(0x8000), 0);
+        assert_eq!(mmc1.cpu_read(0xC000), 7);
+    }
+
+    #[test]
+    fn test_prg_bank_modes() {
+        let mut mmc1 = create_test_mmc1(8);
+
+        load_register(&mut mmc1, 0xE000, 0x05);
+        assert_eq!(mmc1.cpu_read(0x8000), 5);
+        assert_eq!(mmc1.cpu_read(0xFFFF), 7);
+
+        load_register(&mut mmc1, 0x8000, 0b0_1000);
+        assert_eq!(mmc1.cpu_read(0x8000), 0);
+        assert_eq!(mmc1.cpu_read(0xC000), 5);
+
+        load_register(&mut mmc1, 0x8000, 0b0_0000);
+        assert_eq!(mmc1.cpu_read(0x8000), 4);
+        assert_eq!(mmc1.cpu_read(0xC000), 5);
+    }
+
+    #[test]
+    fn test_chr_bank_modes() {
+        let mut mmc1 = create_test_mmc1(2);
+
+        load_register(&mut mmc1, 0xA000, 3);
+        load_register(&mut mmc1, 0xC000, 6);
+        assert_eq!(mmc1.ppu_read(0x0000), 1);
+        assert_eq!(mmc1.ppu_read(0x1000), 1);
+
+        load_register(
+            &mut mmc1,
+            0x8000,
+            CONTROL_CHR_4K_MODE | CONTROL_PRG_MODE_FIX_LAST,
+        );
+        assert_eq!(mmc1.ppu_read(0x0000), 1);
+        assert_eq!(mmc1.ppu_read(0x1000), 3);
+    }
+
+    #[test]
+    fn test_mirroring_control() {
+        let mut mmc1 = create_test_mmc1(2);
+
+        for (control, mirroring) in [
+            (0, Mirroring::SingleScreenLower),
+            (1, Mirroring::SingleScreenUpper),
+            (2, Mirroring::Vertical),
+            (3, Mirroring::Horizontal),
+        ] {
+            load_register(&mut mmc1, 0x8000, control);
+            assert_eq!(mmc1.mirroring(), mirroring);
+        }
+    }
+
+    #[test]
+    fn test_reset_bit_clears_shift_register() {
+        let mut mmc1 = create_test_mmc1(8);
+        load_register(&mut mmc1, 0x8000, 0b0_0000);
+
+        mmc1.cpu_write(0xE000, 1);
+        mmc1.cpu_write(0xE000, 0x80);
+        load_register(&mut mmc1, 0xE000, 0x02);
+
+        assert_eq!(mmc1.prg_bank, 0x02);
+        assert_eq!(
+            mmc1.control & CONTROL_PRG_MODE_FIX_LAST,
+            CONTROL_PRG_MODE_FIX_LAST
+        );
+    }
+
+    #[test]
+    fn test_prg_ram_enable() {
+        let mut mmc1 = create_test_mmc1(2);
+        mmc1.cpu_write(0x6000, 0x42);
+        assert_eq!(mmc1.cpu_read(0x6000), 0x42);
+
+        load_register(&mut mmc1, 0xE000, PRG_BANK_RAM_DISABLE);
+        mmc1.cpu_write(0x6000, 0x99);
+
+        assert_eq!(mmc1.cpu_peek(0x6000), None);
+        load_register(&mut mmc1, 0xE000, 0);
+        assert_eq!(mmc1.cpu_read(0x6000), 0x42);
+    }
+
+    #[test]
+    fn test_write_after_dummy_write_is_ignored() {
+        let mut mmc1 = create_test_mmc1(2);
+
+        mmc1.cpu_dummy_write(0x8000, 1);
+        mmc1.cpu_write(0x8000, 1);
+        assert_eq!(mmc1.write_count, 1);
+
+        mmc1.cpu_write(0x8000, 1);
+        assert_eq!(mmc1.write_count, 2);
+    }
+
+    #[test]
+    fn test_separate_store_instructions_all_land() {
+        // LDA #$01; STA $E000; LDA #$00; STA $E000 x4 selects bank 1, but only if
+        // none of the back-to-back stores is mistaken for an RMW write
+        let program = [
+            0xa9, 0x01, 0x8d, 0x00, 0xe0, 0xa9, 0x00, 0x8d, 0x00, 0xe0, 0x8d, 0x00, 0xe0, 0x8d,
+            0x00, 0xe0, 0x8d, 0x00, 0xe0,
+        ];
+        let mut prg_rom = numbered_banks(4, PRG_ROM_BANK_SIZE);
+        prg_rom[0xC000..0xC000 + program.len()].copy_from_slice(&program);
+        prg_rom[0xFFFC..0xFFFE].copy_from_slice(&[0x00, 0xC0]);
+        let mmc1 = Mmc1::new(cartridge(1, 0, prg_rom, vec![0; CHR_ROM_BANK_SIZE], 0));
+        let mut cpu = CPU::with_bus(Box::new(MemoryMap::new(Box::new(mmc1))));
+        cpu.reset();
+
+        cpu.run_until(|cpu, _| cpu.program_counter == 0xC000 + program.len() as u16)
+            .unwrap();
+
+        assert_eq!(cpu.bus.read(0x8000), 1);
+    }
+
+    #[test]
+    fn test_read_modify_write_reset_idiom() {
+        // INC $8000 on a ROM byte of $FF writes $FF (reset) then $00 (ignored)
+        let mut prg_rom = vec![0; 2 * PRG_ROM_BANK_SIZE];
+        prg_rom[0x4000..0x4004].copy_from_slice(&[0xee, 0x00, 0x80, 0x00]);
+        prg_rom[0] = 0xFF;
+        prg_rom[0x7FFC..0x7FFE].copy_from_slice(&[0x00, 0xC0]);
+        let mmc1 = Mmc1::new(cartridge(1, 0, prg_rom, vec![0; CHR_ROM_BANK_SIZE], 0));
+        let mut cpu = CPU::with_bus(Box::new(MemoryMap::new(Box::new(mmc1))));
+        cpu.reset();
+
+        cpu.bus.write(0x8000, 1);
+        assert_eq!(cpu.step().unwrap().operation_code, 0xee);
+
+        // Had the $00 write landed, the fourth bit below would complete the
+        // register instead of the fifth
+        for bit in [1, 0, 0, 0, 0] {
+            cpu.bus.write(0xE000, bit);
+        }
+        assert_eq!(cpu.bus.read(0x8000), 0xee);
+    }
+}
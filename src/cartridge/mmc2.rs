@@ -0,0 +1,220 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START, PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const MMC2_PRG_BANK_SIZE: usize = 0x2000;
+const MMC4_PRG_BANK_SIZE: usize = 0x4000;
+const CHR_BANK_SIZE: usize = 0x1000;
+const MMC4_MAPPER: u16 = 10;
+
+const LATCH_FD: u8 = 0xFD;
+const LATCH_FE: u8 = 0xFE;
+
+/// Mappers 9 (MMC2) and 10 (MMC4): each 4 KiB pattern table has two CHR banks,
+/// and a latch picks between them whenever the PPU fetches tile $FD or $FE.
+///
+/// The fetch that trips a latch still comes from the old bank. MMC2 switches
+/// 8 KiB of PRG and fixes the last three banks; MMC4 switches 16 KiB and fixes
+/// the last bank.
+pub struct Mmc2 {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    prg_bank_size: usize,
+    prg_bank: usize,
+    // [latch 0 $FD, latch 0 $FE, latch 1 $FD, latch 1 $FE]
+    chr_banks: [usize; 4],
+    latches: [u8; 2],
+    mirroring: Mirroring,
+}
+
+impl Mmc2 {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Mmc2 {
+            prg_ram: cartridge.prg_ram,
+            prg_bank_size: match cartridge.header.mapper {
+                MMC4_MAPPER => MMC4_PRG_BANK_SIZE,
+                _ => MMC2_PRG_BANK_SIZE,
+            },
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            prg_bank: 0,
+            chr_banks: [0; 4],
+            latches: [LATCH_FE; 2],
+        }
+    }
+
+    fn prg_rom_index(&self, address: u16) -> usize {
+        let offset = (address - PRG_ROM_START) as usize;
+        let slot = offset / self.prg_bank_size;
+        let bank_count = self.prg_rom.len() / self.prg_bank_size;
+        // Slot 0 is switchable; the rest map to the banks at the end of the ROM
+        let slot_count = 0x8000 / self.prg_bank_size;
+        let bank = match slot {
+            0 => self.prg_bank,
+            _ => (bank_count + slot).saturating_sub(slot_count),
+        };
+        (bank * self.prg_bank_size + offset % self.prg_bank_size) % self.prg_rom.len()
+    }
+
+    fn chr_index(&self, address: u16) -> usize {
+        let table = (address as usize % 0x2000) / CHR_BANK_SIZE;
+        let bank = match self.latches[table] {
+            LATCH_FD => self.chr_banks[table * 2],
+            _ => self.chr_banks[table * 2 + 1],
+        };
+        (bank * CHR_BANK_SIZE + address as usize % CHR_BANK_SIZE) % self.chr.len()
+    }
+
+    // MMC2 only watches $0FD8 for the left table; every other trigger spans the
+    // 8 bytes of the tile's second bitplane
+    fn update_latch(&mut self, address: u16) {
+        let table = (address as usize % 0x2000) / CHR_BANK_SIZE;
+        let exact = table == 0 && self.prg_bank_size == MMC2_PRG_BANK_SIZE;
+        let tile = match address & 0x0FF8 {
+            0x0FD8 if !exact || address & 0x0FFF == 0x0FD8 => LATCH_FD,
+            0x0FE8 if !exact || address & 0x0FFF == 0x0FE8 => LATCH_FE,
+            _ => return,
+        };
+        self.latches[table] = tile;
+    }
+}
+
+impl Mapper for Mmc2 {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => write_prg_ram(&mut self.prg_ram, address, value),
+            0xA000..=0xAFFF => self.prg_bank = (value & 0x0F) as usize,
+            0xB000..=0xEFFF => {
+                let register = ((address - 0xB000) >> 12) as usize;
+                self.chr_banks[register] = (value & 0x1F) as usize;
+            }
+            0xF000..=0xFFFF => {
+                self.mirroring = match value & 1 {
+                    0 => Mirroring::Vertical,
+                    _ => Mirroring::Horizontal,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => read_prg_ram(&self.prg_ram, address),
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                Some(self.prg_rom[self.prg_rom_index(address)])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        let value = self.chr[self.chr_index(address)];
+        self.update_latch(address);
+        value
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let index = self.chr_index(address);
+            self.chr[index] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::PRG_ROM_BANK_SIZE;
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    fn create_test_mmc2(mapper: u16) -> Mmc2 {
+        let mut mmc2 = Mmc2::new(cartridge(
+            mapper,
+            0,
+            numbered_banks(8, PRG_ROM_BANK_SIZE),
+            numbered_banks(16, CHR_BANK_SIZE),
+            0,
+        ));
+        for (register, bank) in [(0xB000, 1), (0xC000, 2), (0xD000, 3), (0xE000, 4)] {
+            mmc2.cpu_write(register, bank);
+        }
+        mmc2
+    }
+
+    #[test]
+    fn test_mmc2_prg_layout() {
+        let mut mmc2 = create_test_mmc2(9);
+        mmc2.cpu_write(0xA000, 5);
+
+        // 8 KiB banks read back as half of a numbered 16 KiB bank
+        assert_eq!(mmc2.cpu_read(0x8000), 2);
+        assert_eq!(mmc2.cpu_read(0xA000), 6);
+        assert_eq!(mmc2.cpu_read(0xC000), 7);
+        assert_eq!(mmc2.cpu_read(0xFFFF), 7);
+    }
+
+    #[test]
+    fn test_mmc4_prg_layout() {
+        let mut mmc4 = create_test_mmc2(10);
+        mmc4.cpu_write(0xA000, 5);
+
+        assert_eq!(mmc4.cpu_read(0x8000), 5);
+        assert_eq!(mmc4.cpu_read(0xC000), 7);
+    }
+
+    #[test]
+    fn test_tile_fetches_flip_latches_after_the_fetch() {
+        let mut mmc2 = create_test_mmc2(9);
+        assert_eq!(mmc2.ppu_read(0x0000), 2);
+        assert_eq!(mmc2.ppu_read(0x1000), 4);
+
+        assert_eq!(mmc2.ppu_read(0x0FD8), 2);
+        assert_eq!(mmc2.ppu_read(0x0000), 1);
+
+        assert_eq!(mmc2.ppu_read(0x1FDC), 4);
+        assert_eq!(mmc2.ppu_read(0x1000), 3);
+
+        mmc2.ppu_read(0x1FE8);
+        assert_eq!(mmc2.ppu_read(0x1000), 4);
+    }
+
+    #[test]
+    fn test_mmc2_left_latch_only_triggers_on_first_byte() {
+        let mut mmc2 = create_test_mmc2(9);
+        mmc2.ppu_read(0x0FD9);
+        assert_eq!(mmc2.ppu_read(0x0000), 2);
+
+        let mut mmc4 = create_test_mmc2(10);
+        mmc4.ppu_read(0x0FD9);
+        assert_eq!(mmc4.ppu_read(0x0000), 1);
+    }
+
+    #[test]
+    fn test_mirroring_register() {
+        let mut mmc2 = create_test_mmc2(9);
+
+        mmc2.cpu_write(0xF000, 1);
+
+        assert_eq!(mmc2.mirroring(), Mirroring::Horizontal);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START, PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const PRG_BANK_SIZE: usize = 0x2000;
+const CHR_BANK_SIZE: usize = 0x0400;
+const CHR_INVERSION_XOR: u16 = 0x1000;
+
+const BANK_SELECT_REGISTER_MASK: u8 = 0b0000_0111;
+const BANK_SELECT_PRG_MODE: u8 = 0b0100_0000;
+const BANK_SELECT_CHR_INVERSION: u8 = 0b1000_0000;
+const PRG_RAM_ENABLE: u8 = 0b1000_0000;
+const PRG_RAM_WRITE_PROTECT: u8 = 0b0100_0000;
+
+const PPU_A12: u16 = 0x1000;
+// A12 has to stay low this many accesses before a rise clocks the counter, so the
+// nametable fetches between sprite pattern fetches do not count as new scanlines
+const A12_LOW_ACCESSES: u8 = 3;
+
+/// Mapper 4: eight bank registers, PRG and CHR inversion modes and a scanline
+/// counter clocked by rising edges of PPU address line A12.
+pub struct Mmc3 {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    four_screen: bool,
+    bank_select: u8,
+    bank_registers: [u8; 8],
+    mirroring: Mirroring,
+    prg_ram_protect: u8,
+    irq_latch: u8,
+    irq_counter: u8,
+    irq_reload: bool,
+    irq_enabled: bool,
+    irq_pending: bool,
+    a12_low_accesses: u8,
+}
+
+impl Mmc3 {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Mmc3 {
+            prg_ram: cartridge.prg_ram,
+            four_screen: cartridge.header.mirroring == Mirroring::FourScreen,
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            bank_select: 0,
+            bank_registers: [0, 2, 4, 5, 6, 7, 0, 1],
+            prg_ram_protect: PRG_RAM_ENABLE,
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_reload: false,
+            irq_enabled: false,
+            irq_pending: false,
+            a12_low_accesses: 0,
+        }
+    }
+
+    fn prg_rom_index(&self, address: u16) -> usize {
+        let bank_count = self.prg_rom.len() / PRG_BANK_SIZE;
+        let second_last = bank_count.saturating_sub(2);
+        let slot = ((address - PRG_ROM_START) as usize) / PRG_BANK_SIZE;
+        let bank = match (slot, self.bank_select & BANK_SELECT_PRG_MODE != 0) {
+            (0, false) | (2, true) => self.bank_registers[6] as usize,
+            (0, true) | (2, false) => second_last,
+            (1, _) => self.bank_registers[7] as usize,
+            _ => bank_count.saturating_sub(1),
+        };
+        let offset = address as usize % PRG_BANK_SIZE;
+        (bank * PRG_BANK_SIZE + offset) % self.prg_rom.len()
+    }
+
+    fn chr_index(&self, address: u16) -> usize {
+        let address = match self.bank_select & BANK_SELECT_CHR_INVERSION != 0 {
+            true => address ^ CHR_INVERSION_XOR,
+            false => address,
+        };
+        let slot = (address as usize % 0x2000) / CHR_BANK_SIZE;
+        // R0 and R1 select 2 KiB banks, ignoring their low bit
+        let bank = match slot {
+            0..=3 => (self.bank_registers[slot / 2] & !1) | (slot as u8 & 1),
+            _ => self.bank_registers[slot - 2],
+        };
+        let offset = address as usize % CHR_BANK_SIZE;
+        (bank as usize * CHR_BANK_SIZE + offset) % self.chr.len()
+    }
+
+    fn clock_irq_counter(&mut self) {
+        if self.irq_counter == 0 || self.irq_reload {
+            self.irq_counter = self.irq_latch;
+            self.irq_reload = false;
+        } else {
+            self.irq_counter -= 1;
+        }
+        if self.irq_counter == 0 && self.irq_enabled {
+            self.irq_pending = true;
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        let even = address & 1 == 0;
+        match (address, even) {
+            (0x8000..=0x9FFF, true) => self.bank_select = value,
+            (0x8000..=0x9FFF, false) => {
+                let register = (self.bank_select & BANK_SELECT_REGISTER_MASK) as usize;
+                self.bank_registers[register] = value;
+            }
+            (0xA000..=0xBFFF, true) => {
+                if !self.four_screen {
+                    self.mirroring = match value & 1 {
+                        0 => Mirroring::Vertical,
+                        _ => Mirroring::Horizontal,
+                    };
+                }
+            }
+            (0xA000..=0xBFFF, false) => self.prg_ram_protect = value,
+            (0xC000..=0xDFFF, true) => self.irq_latch = value,
+            (0xC000..=0xDFFF, false) => {
+                self.irq_counter = 0;
+                self.irq_reload = true;
+            }
+            (_, true) => {
+                self.irq_enabled = false;
+                self.irq_pending = false;
+            }
+            (_, false) => self.irq_enabled = true,
+        }
+    }
+
+    fn prg_ram_readable(&self) -> bool {
+        !self.prg_ram.is_empty() && self.prg_ram_protect & PRG_RAM_ENABLE != 0
+    }
+}
+
+impl Mapper for Mmc3 {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END
+                if self.prg_ram_readable() && self.prg_ram_protect & PRG_RAM_WRITE_PROTECT == 0 =>
+            {
+                write_prg_ram(&mut self.prg_ram, address, value)
+            }
+            PRG_ROM_START..=0xFFFF => self.write_register(address, value),
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END if self.prg_ram_readable() => {
+                read_prg_ram(&self.prg_ram, address)
+            }
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                Some(self.prg_rom[self.prg_rom_index(address)])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[self.chr_index(address)]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let index = self.chr_index(address);
+            self.chr[index] = value;
+        }
+    }
+
+    fn ppu_address(&mut self, address: u16) {
+        if address & PPU_A12 == 0 {
+            self.a12_low_accesses = self.a12_low_accesses.saturating_add(1);
+            return;
+        }
+        if self.a12_low_accesses >= A12_LOW_ACCESSES {
+            self.clock_irq_counter();
+        }
+        self.a12_low_accesses = 0;
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+
+    fn irq(&self) -> bool {
+        self.irq_pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::CHR_ROM_BANK_SIZE;
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+    use crate::cpu::cpu_model::CPU;
+    use crate::cpu::interrupt::Interrupt;
+    use crate::cpu::memory_map::MemoryMap;
+
+    fn create_test_mmc3() -> Mmc3 {
+        Mmc3::new(cartridge(
+            4,
+            0,
+            numbered_banks(16, PRG_BANK_SIZE),
+            numbered_banks(32, CHR_BANK_SIZE),
+            0,
+        ))
+    }
+
+    // One rendered scanline: background fetches from $0000, sprites from $1000
+    fn render_scanline(mmc3: &mut Mmc3) {
+        for _ in 0..4 {
+            mmc3.ppu_address(0x2000);
+            mmc3.ppu_address(0x0000);
+        }
+        for _ in 0..8 {
+            mmc3.ppu_address(0x2000);
+            mmc3.ppu_address(0x2000);
+            mmc3.ppu_address(0x1000);
+            mmc3.ppu_address(0x1008);
+        }
+    }
+
+    #[test]
+    fn test_prg_modes_swap_r6_and_second_last_bank() {
+        let mut mmc3 = create_test_mmc3();
+        mmc3.cpu_write(0x8000, 6);
+        mmc3.cpu_write(0x8001, 3);
+        mmc3.cpu_write(0x8000, 7);
+        mmc3.cpu_write(0x8001, 5);
+
+        assert_eq!(mmc3.cpu_read(0x8000), 3);
+        assert_eq!(mmc3.cpu_read(0xA000), 5);
+        assert_eq!(mmc3.cpu_read(0xC000), 14);
+        assert_eq!(mmc3.cpu_read(0xE000), 15);
+
+        mmc3.cpu_write(0x8000, BANK_SELECT_PRG_MODE);
+        assert_eq!(mmc3.cpu_read(0x8000), 14);
+        assert_eq!(mmc3.cpu_read(0xC000), 3);
+    }
+
+    #[test]
+    fn test_chr_banks_and_inversion() {
+        let mut mmc3 = create_test_mmc3();
+        for (register, bank) in [(0, 9), (1, 12), (2, 20), (5, 31)] {
+            mmc3.cpu_write(0x8000, register);
+            mmc3.cpu_write(0x8001, bank);
+        }
+
+        assert_eq!(mmc3.ppu_read(0x0000), 8);
+        assert_eq!(mmc3.ppu_read(0x0400), 9);
+        assert_eq!(mmc3.ppu_read(0x0C00), 13);
+        assert_eq!(mmc3.ppu_read(0x1000), 20);
+        assert_eq!(mmc3.ppu_read(0x1C00), 31);
+
+        mmc3.cpu_write(0x8000, BANK_SELECT_CHR_INVERSION);
+        assert_eq!(mmc3.ppu_read(0x0000), 20);
+        assert_eq!(mmc3.ppu_read(0x1400), 9);
+    }
+
+    #[test]
+    fn test_mirroring_and_prg_ram_protect() {
+        let mut mmc3 = create_test_mmc3();
+        mmc3.cpu_write(0xA000, 1);
+        assert_eq!(mmc3.mirroring(), Mirroring::Horizontal);
+
+        mmc3.cpu_write(0x6000, 0x11);
+        mmc3.cpu_write(0xA001, PRG_RAM_ENABLE | PRG_RAM_WRITE_PROTECT);
+        mmc3.cpu_write(0x6000, 0x22);
+        assert_eq!(mmc3.cpu_read(0x6000), 0x11);
+
+        mmc3.cpu_write(0xA001, 0);
+        assert_eq!(mmc3.cpu_peek(0x6000), None);
+    }
+
+    #[test]
+    fn test_irq_counter_counts_filtered_a12_rises() {
+        let mut mmc3 = create_test_mmc3();
+        mmc3.cpu_write(0xC000, 2);
+        mmc3.cpu_write(0xC001, 0);
+        mmc3.cpu_write(0xE001, 0);
+
+        // Reload, then 2 and 1: the third scanline fires
+        render_scanline(&mut mmc3);
+        render_scanline(&mut mmc3);
+        assert!(!mmc3.irq());
+        render_scanline(&mut mmc3);
+        assert!(mmc3.irq());
+
+        mmc3.cpu_write(0xE000, 0);
+        assert!(!mmc3.irq());
+        render_scanline(&mut mmc3);
+        assert!(!mmc3.irq());
+    }
+
+    #[test]
+    fn test_mapper_irq_reaches_the_cpu() {
+        let mut prg_rom = numbered_banks(4, PRG_BANK_SIZE);
+        // Reset to a CLI at $E000, IRQ handler at $E100
+        prg_rom[0x6000] = 0x58;
+        prg_rom[0x7FFC..].copy_from_slice(&[0x00, 0xE0, 0x00, 0xE1]);
+        let mut mmc3 = Mmc3::new(cartridge(4, 0, prg_rom, vec![0; CHR_ROM_BANK_SIZE], 0));
+        // A zero latch fires on the first clock
+        mmc3.cpu_write(0xE001, 0);
+        render_scanline(&mut mmc3);
+        let mut cpu = CPU::with_bus(Box::new(MemoryMap::new(Box::new(mmc3))));
+        cpu.reset();
+
+        // Reset masks IRQs until the CLI has run
+        let cli = cpu.step().unwrap();
+        assert_eq!(cli.interrupt, None);
+        assert_eq!(cli.operation_code, 0x58);
+
+        let step = cpu.step().unwrap();
+        assert_eq!(step.interrupt, Some(Interrupt::Irq));
+        assert_eq!(cpu.program_counter, 0xE100);
+    }
+
+    #[test]
+    fn test_four_screen_ignores_mirroring_writes() {
+        let mut mmc3 = Mmc3::new(cartridge(
+            4,
+            0,
+            numbered_banks(4, PRG_BANK_SIZE),
+            vec![0; CHR_ROM_BANK_SIZE],
+            0b0000_1000,
+        ));
+
+        mmc3.cpu_write(0xA000, 1);
+
+        assert_eq!(mmc3.mirroring(), Mirroring::FourScreen);
+    }
+}
@@ -0,0 +1,19 @@
+pub mod axrom;
+pub mod cartridge_error;
+pub mod cartridge_model;
+pub mod cnrom;
+pub mod console_type;
+pub mod header;
+pub mod header_format;
+pub mod mapper;
+pub mod mirroring;
+pub mod mmc1;
+pub mod mmc2;
+pub mod mmc3;
+pub mod nrom;
+pub mod save_file;
+#[cfg(test)]
+pub(crate) mod test_rom;
+pub mod timing_region;
+pub mod uxrom;
+pub mod vrc4;
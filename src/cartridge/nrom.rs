@@ -0,0 +1,151 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START, PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+/// Mapper 0: 16 or 32 KiB of PRG-ROM and 8 KiB of CHR, no bank switching.
+///
+/// A 16 KiB image is mirrored at $8000 and $C000.
+pub struct Nrom {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    mirroring: Mirroring,
+}
+
+impl Nrom {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Nrom {
+            prg_ram: cartridge.prg_ram,
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+        }
+    }
+}
+
+impl Mapper for Nrom {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        if let PRG_RAM_START..=PRG_RAM_END = address {
+            write_prg_ram(&mut self.prg_ram, address, value);
+        }
+    }
+
+    // Unmapped addresses are open bus, reported as `None`
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => read_prg_ram(&self.prg_ram, address),
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                let index = (address - PRG_ROM_START) as usize % self.prg_rom.len();
+                Some(self.prg_rom[index])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[address as usize % self.chr.len()]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let len = self.chr.len();
+            self.chr[address as usize % len] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    #[test]
+    fn test_nrom_128_mirrors_prg_rom() {
+        let mut nrom = Nrom::new(cartridge(
+            0,
+            0,
+            numbered_banks(1, PRG_ROM_BANK_SIZE),
+            numbered_banks(1, CHR_ROM_BANK_SIZE),
+            0b0000_0001,
+        ));
+        nrom.prg_rom[0x0123] = 0x42;
+
+        assert_eq!(nrom.cpu_read(0x8123), 0x42);
+        assert_eq!(nrom.cpu_read(0xC123), 0x42);
+        assert_eq!(nrom.mirroring(), Mirroring::Vertical);
+    }
+
+    #[test]
+    fn test_nrom_256_maps_both_banks() {
+        let mut nrom = Nrom::new(cartridge(
+            0,
+            0,
+            numbered_banks(2, PRG_ROM_BANK_SIZE),
+            numbered_banks(1, CHR_ROM_BANK_SIZE),
+            0,
+        ));
+
+        assert_eq!(nrom.cpu_read(0x8000), 0);
+        assert_eq!(nrom.cpu_read(0xFFFF), 1);
+
+        nrom.cpu_write(0x8000, 0x55);
+        assert_eq!(nrom.cpu_read(0x8000), 0);
+    }
+
+    #[test]
+    fn test_nrom_prg_ram_and_open_bus() {
+        let mut nrom = Nrom::new(cartridge(
+            0,
+            0,
+            numbered_banks(1, PRG_ROM_BANK_SIZE),
+            numbered_banks(1, CHR_ROM_BANK_SIZE),
+            0,
+        ));
+
+        nrom.cpu_write(0x6010, 0x99);
+
+        assert_eq!(nrom.cpu_read(0x6010), 0x99);
+        assert_eq!(nrom.cpu_peek(0x5000), None);
+    }
+
+    #[test]
+    fn test_nrom_chr_rom_is_read_only_and_chr_ram_is_writable() {
+        let mut chr_rom = Nrom::new(cartridge(
+            0,
+            0,
+            numbered_banks(1, PRG_ROM_BANK_SIZE),
+            vec![0x11; CHR_ROM_BANK_SIZE],
+            0,
+        ));
+        chr_rom.ppu_write(0x0010, 0x22);
+        assert_eq!(chr_rom.ppu_read(0x0010), 0x11);
+
+        let mut chr_ram = Nrom::new(cartridge(
+            0,
+            0,
+            numbered_banks(1, PRG_ROM_BANK_SIZE),
+            Vec::new(),
+            0,
+        ));
+        chr_ram.ppu_write(0x1FFF, 0x22);
+        assert_eq!(chr_ram.ppu_read(0x1FFF), 0x22);
+    }
+}
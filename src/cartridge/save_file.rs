@@ -0,0 +1,87 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// The `.sav` file that sits next to a ROM and holds its battery-backed PRG-RAM.
+pub fn save_path<P: AsRef<Path>>(rom_path: P) -> PathBuf {
+    rom_path.as_ref().with_extension("sav")
+}
+
+/// Where a battery-backed cartridge keeps its PRG-RAM between sessions.
+///
+/// `Cartridge::from_file` loads it, and the `MemoryMap` built from that cartridge
+/// keeps it to flush the mapper's RAM on shutdown or when the player saves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveFile {
+    path: PathBuf,
+}
+
+impl SaveFile {
+    pub fn for_rom<P: AsRef<Path>>(rom_path: P) -> Self {
+        SaveFile {
+            path: save_path(rom_path),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fills `prg_ram` from the save, returning `false` when there is none yet.
+    ///
+    /// A file of the wrong size still loads as much as fits, so saves from emulators
+    /// that pad or trim the RAM keep working.
+    pub fn load(&self, prg_ram: &mut [u8]) -> Result<bool, CartridgeError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(CartridgeError::Io(error.to_string())),
+        };
+        let len = bytes.len().min(prg_ram.len());
+        prg_ram[..len].copy_from_slice(&bytes[..len]);
+        Ok(true)
+    }
+
+    pub fn flush(&self, prg_ram: &[u8]) -> Result<(), CartridgeError> {
+        fs::write(&self.path, prg_ram).map_err(|error| CartridgeError::Io(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom::temp_path;
+
+    #[test]
+    fn test_save_path_replaces_extension() {
+        assert_eq!(save_path("roms/zelda.nes"), PathBuf::from("roms/zelda.sav"));
+    }
+
+    #[test]
+    fn test_flush_and_load_round_trip() {
+        let save_file = SaveFile::for_rom(temp_path("round_trip.nes"));
+        save_file.flush(&[1, 2, 3, 4]).unwrap();
+
+        let mut smaller = [0; 2];
+        let mut larger = [0xFF; 6];
+        assert!(save_file.load(&mut smaller).unwrap());
+        assert!(save_file.load(&mut larger).unwrap());
+        fs::remove_file(save_file.path()).unwrap();
+
+        assert_eq!(smaller, [1, 2]);
+        assert_eq!(larger, [1, 2, 3, 4, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_load_without_file() {
+        let mut prg_ram = [0x42; 4];
+
+        let found = SaveFile::for_rom("does/not/exist.nes")
+            .load(&mut prg_ram)
+            .unwrap();
+
+        assert!(!found);
+        assert_eq!(prg_ram, [0x42; 4]);
+    }
+}
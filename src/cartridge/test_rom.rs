@@ -0,0 +1,62 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::header::{CHR_ROM_BANK_SIZE, HEADER_SIZE, PRG_ROM_BANK_SIZE};
+use crate::cartridge::nrom::Nrom;
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+const FLAGS_6_BATTERY: u8 = 0b0000_0010;
+const NES2_IDENTIFIER: u8 = 0b0000_1000;
+// 64 << 7 = 8 KiB
+const RAM_SIZE_8_KIB: u8 = 7;
+
+/// `count` banks of `size` bytes, each filled with its own index, so tests can
+/// tell which bank is mapped by reading any byte of it.
+pub fn numbered_banks(count: usize, size: usize) -> Vec<u8> {
+    (0..count).flat_map(|bank| vec![bank as u8; size]).collect()
+}
+
+/// A file in the temp directory that no other test, or concurrent test run, uses.
+pub fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("nes_pcfim_{}_{}", process::id(), name))
+}
+
+/// Builds a NES 2.0 image in memory and parses it. An empty `chr_rom` gives the
+/// board 8 KiB of CHR-RAM; the battery flag in `flags_6` turns PRG-RAM into NVRAM.
+pub fn cartridge(
+    mapper: u16,
+    submapper: u8,
+    prg_rom: Vec<u8>,
+    chr_rom: Vec<u8>,
+    flags_6: u8,
+) -> Cartridge {
+    let mut bytes = vec![0; HEADER_SIZE];
+    bytes[..4].copy_from_slice(b"NES\x1a");
+    bytes[4] = (prg_rom.len() / PRG_ROM_BANK_SIZE) as u8;
+    bytes[5] = (chr_rom.len() / CHR_ROM_BANK_SIZE) as u8;
+    bytes[6] = flags_6 | ((mapper as u8 & 0x0F) << 4);
+    bytes[7] = NES2_IDENTIFIER | (mapper as u8 & 0xF0);
+    bytes[8] = (submapper << 4) | (mapper >> 8) as u8;
+    bytes[10] = match flags_6 & FLAGS_6_BATTERY {
+        0 => RAM_SIZE_8_KIB,
+        _ => RAM_SIZE_8_KIB << 4,
+    };
+    if chr_rom.is_empty() {
+        bytes[11] = RAM_SIZE_8_KIB;
+    }
+    bytes.extend(prg_rom);
+    bytes.extend(chr_rom);
+    Cartridge::from_bytes(&bytes).unwrap()
+}
+
+/// An NROM-128 board with blank PRG-ROM and CHR-RAM, for tests that only need
+/// pattern tables and mirroring.
+pub fn nrom(flags_6: u8) -> Nrom {
+    Nrom::new(cartridge(
+        0,
+        0,
+        vec![0; PRG_ROM_BANK_SIZE],
+        Vec::new(),
+        flags_6,
+    ))
+}
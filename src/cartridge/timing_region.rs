@@ -0,0 +1,8 @@
+/// The CPU/PPU timing the image was made for (NES 2.0 byte 12).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingRegion {
+    Ntsc,
+    Pal,
+    MultipleRegion,
+    Dendy,
+}
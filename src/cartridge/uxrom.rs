@@ -0,0 +1,142 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, has_bus_conflicts, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START,
+    PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const PRG_FIXED_START: u16 = 0xC000;
+const PRG_BANK_SIZE: usize = 0x4000;
+
+/// Mapper 2: a switchable 16 KiB PRG bank at $8000 and the last bank fixed at
+/// $C000. CHR is usually 8 KiB of RAM.
+pub struct Uxrom {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    mirroring: Mirroring,
+    bus_conflicts: bool,
+    prg_bank: usize,
+}
+
+impl Uxrom {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        Uxrom {
+            bus_conflicts: has_bus_conflicts(&cartridge, true),
+            prg_ram: cartridge.prg_ram,
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            prg_bank: 0,
+        }
+    }
+
+    fn prg_rom_index(&self, address: u16) -> usize {
+        let bank = match address >= PRG_FIXED_START {
+            true => (self.prg_rom.len() / PRG_BANK_SIZE).saturating_sub(1),
+            false => self.prg_bank,
+        };
+        let offset = (address - PRG_ROM_START) as usize % PRG_BANK_SIZE;
+        (bank * PRG_BANK_SIZE + offset) % self.prg_rom.len()
+    }
+}
+
+impl Mapper for Uxrom {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => write_prg_ram(&mut self.prg_ram, address, value),
+            PRG_ROM_START..=0xFFFF => {
+                let value = match self.bus_conflicts {
+                    true => value & self.cpu_peek(address).unwrap_or(0xFF),
+                    false => value,
+                };
+                self.prg_bank = value as usize;
+            }
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => read_prg_ram(&self.prg_ram, address),
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                Some(self.prg_rom[self.prg_rom_index(address)])
+            }
+            _ => None,
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[address as usize % self.chr.len()]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let len = self.chr.len();
+            self.chr[address as usize % len] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::PRG_ROM_BANK_SIZE;
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    #[test]
+    fn test_uxrom_switches_low_bank_and_fixes_last() {
+        let mut uxrom = Uxrom::new(cartridge(
+            2,
+            1,
+            numbered_banks(8, PRG_ROM_BANK_SIZE),
+            Vec::new(),
+            0,
+        ));
+        assert_eq!(uxrom.cpu_read(0x8000), 0);
+        assert_eq!(uxrom.cpu_read(0xC000), 7);
+
+        uxrom.cpu_write(0x8000, 5);
+
+        assert_eq!(uxrom.cpu_read(0xBFFF), 5);
+        assert_eq!(uxrom.cpu_read(0xFFFF), 7);
+    }
+
+    #[test]
+    fn test_uxrom_bus_conflicts_by_submapper() {
+        let mut prg_rom = numbered_banks(8, PRG_ROM_BANK_SIZE);
+        prg_rom[0x7 * PRG_ROM_BANK_SIZE] = 0x03;
+        let mut uxrom = Uxrom::new(cartridge(2, 2, prg_rom.clone(), Vec::new(), 0));
+
+        uxrom.cpu_write(0xC000, 0x06);
+        assert_eq!(uxrom.cpu_read(0x8000), 2);
+
+        let mut without_conflicts = Uxrom::new(cartridge(2, 1, prg_rom, Vec::new(), 0));
+        without_conflicts.cpu_write(0xC000, 0x06);
+        assert_eq!(without_conflicts.cpu_read(0x8000), 6);
+    }
+
+    #[test]
+    fn test_uxrom_smaller_than_one_bank() {
+        let mut cartridge = cartridge(2, 1, vec![0x42; PRG_ROM_BANK_SIZE], Vec::new(), 0);
+        cartridge.prg_rom.truncate(PRG_ROM_BANK_SIZE / 2);
+        let mut uxrom = Uxrom::new(cartridge);
+
+        assert_eq!(uxrom.cpu_read(0xC000), 0x42);
+    }
+}
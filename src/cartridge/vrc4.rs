@@ -0,0 +1,343 @@
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{
+    chr_memory, read_prg_ram, write_prg_ram, Mapper, PRG_RAM_END, PRG_RAM_START, PRG_ROM_START,
+};
+use crate::cartridge::mirroring::Mirroring;
+
+const PRG_BANK_SIZE: usize = 0x2000;
+const CHR_BANK_SIZE: usize = 0x0400;
+
+const PRG_SWAP_MODE: u8 = 0b10;
+const IRQ_ENABLE_AFTER_ACKNOWLEDGE: u8 = 0b001;
+const IRQ_ENABLE: u8 = 0b010;
+const IRQ_CYCLE_MODE: u8 = 0b100;
+// The prescaler counts down by 3 per CPU cycle from 341, one scanline of PPU dots
+const IRQ_PRESCALER_RELOAD: i16 = 341;
+const IRQ_PRESCALER_STEP: i16 = 3;
+
+/// Konami VRC2 and VRC4 (mappers 21, 22, 23 and 25): two switchable 8 KiB PRG
+/// banks, eight 1 KiB CHR banks and, on VRC4, a CPU-cycle IRQ counter with a
+/// scanline prescaler.
+///
+/// The boards wire different CPU address lines to the chip's two register select
+/// pins; the NES 2.0 submapper picks the wiring. Submapper 0 listens on both
+/// candidate lines, which works for every game that only uses one of them.
+pub struct Vrc4 {
+    prg_rom: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool,
+    prg_ram: Vec<u8>,
+    vrc2: bool,
+    register_select_lines: [u16; 2],
+    // VRC2a drops the low bit of every CHR bank number
+    chr_bank_shift: u8,
+    prg_banks: [u8; 2],
+    prg_swap_mode: bool,
+    chr_banks: [u16; 8],
+    mirroring: Mirroring,
+    irq_latch: u8,
+    irq_counter: u8,
+    irq_control: u8,
+    irq_prescaler: i16,
+    irq_pending: bool,
+}
+
+// (A0 lines, A1 lines, is VRC2) for a mapper and submapper
+fn board_wiring(mapper: u16, submapper: u8) -> (u16, u16, bool) {
+    match (mapper, submapper) {
+        (21, 1) => (0x02, 0x04, false),
+        (21, 2) => (0x40, 0x80, false),
+        (21, _) => (0x42, 0x84, false),
+        (22, _) => (0x02, 0x01, true),
+        (23, 1) => (0x01, 0x02, false),
+        (23, 2) => (0x04, 0x08, false),
+        (23, 3) => (0x01, 0x02, true),
+        (23, _) => (0x05, 0x0A, false),
+        (25, 1) => (0x02, 0x01, false),
+        (25, 2) => (0x08, 0x04, false),
+        (25, 3) => (0x02, 0x01, true),
+        _ => (0x0A, 0x05, false),
+    }
+}
+
+impl Vrc4 {
+    pub fn new(cartridge: Cartridge) -> Self {
+        let (chr, chr_is_ram) = chr_memory(&cartridge);
+        let (a0, a1, vrc2) = board_wiring(cartridge.header.mapper, cartridge.header.submapper);
+        Vrc4 {
+            prg_ram: cartridge.prg_ram,
+            chr_bank_shift: (cartridge.header.mapper == 22) as u8,
+            mirroring: cartridge.header.mirroring,
+            prg_rom: cartridge.prg_rom,
+            chr,
+            chr_is_ram,
+            vrc2,
+            register_select_lines: [a0, a1],
+            prg_banks: [0, 1],
+            prg_swap_mode: false,
+            chr_banks: [0; 8],
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_control: 0,
+            irq_prescaler: IRQ_PRESCALER_RELOAD,
+            irq_pending: false,
+        }
+    }
+
+    // Folds the board's address lines down to the chip's register number 0-3
+    fn register(&self, address: u16) -> u16 {
+        let [a0, a1] = self.register_select_lines;
+        (address & a0 != 0) as u16 | ((address & a1 != 0) as u16) << 1
+    }
+
+    fn prg_rom_index(&self, address: u16) -> usize {
+        let bank_count = self.prg_rom.len() / PRG_BANK_SIZE;
+        let second_last = bank_count.saturating_sub(2);
+        let slot = (address - PRG_ROM_START) as usize / PRG_BANK_SIZE;
+        let bank = match (slot, self.prg_swap_mode) {
+            (0, false) | (2, true) => self.prg_banks[0] as usize,
+            (0, true) | (2, false) => second_last,
+            (1, _) => self.prg_banks[1] as usize,
+            _ => bank_count.saturating_sub(1),
+        };
+        (bank * PRG_BANK_SIZE + address as usize % PRG_BANK_SIZE) % self.prg_rom.len()
+    }
+
+    fn chr_index(&self, address: u16) -> usize {
+        let slot = (address as usize % 0x2000) / CHR_BANK_SIZE;
+        let bank = (self.chr_banks[slot] >> self.chr_bank_shift) as usize;
+        (bank * CHR_BANK_SIZE + address as usize % CHR_BANK_SIZE) % self.chr.len()
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        let register = self.register(address);
+        match (address & 0xF000, register) {
+            (0x8000, _) => self.prg_banks[0] = value & 0x1F,
+            (0x9000, 0 | 1) if self.vrc2 => {
+                self.mirroring = match value & 1 {
+                    0 => Mirroring::Vertical,
+                    _ => Mirroring::Horizontal,
+                }
+            }
+            (0x9000, 0 | 1) => {
+                self.mirroring = match value & 0b11 {
+                    0 => Mirroring::Vertical,
+                    1 => Mirroring::Horizontal,
+                    2 => Mirroring::SingleScreenLower,
+                    _ => Mirroring::SingleScreenUpper,
+                }
+            }
+            (0x9000, _) if !self.vrc2 => self.prg_swap_mode = value & PRG_SWAP_MODE != 0,
+            (0xA000, _) => self.prg_banks[1] = value & 0x1F,
+            (0xB000..=0xE000, _) => {
+                let slot = ((address >> 12) - 0xB) as usize * 2 + (register >> 1) as usize;
+                let bank = self.chr_banks[slot];
+                self.chr_banks[slot] = match register & 1 {
+                    0 => (bank & 0x1F0) | (value & 0x0F) as u16,
+                    _ => (bank & 0x00F) | ((value & 0x1F) as u16) << 4,
+                };
+            }
+            (0xF000, _) if self.vrc2 => {}
+            (0xF000, 0) => self.irq_latch = (self.irq_latch & 0xF0) | (value & 0x0F),
+            (0xF000, 1) => self.irq_latch = (self.irq_latch & 0x0F) | (value << 4),
+            (0xF000, 2) => {
+                self.irq_control = value & 0b111;
+                self.irq_pending = false;
+                if self.irq_control & IRQ_ENABLE != 0 {
+                    self.irq_counter = self.irq_latch;
+                    self.irq_prescaler = IRQ_PRESCALER_RELOAD;
+                }
+            }
+            (0xF000, _) => {
+                self.irq_pending = false;
+                // Acknowledging copies the "enable after acknowledge" bit into enable
+                let enable = (self.irq_control & IRQ_ENABLE_AFTER_ACKNOWLEDGE) << 1;
+                self.irq_control = (self.irq_control & !IRQ_ENABLE) | enable;
+            }
+            _ => {}
+        }
+    }
+
+    fn clock_irq_counter(&mut self) {
+        if self.irq_counter == 0xFF {
+            self.irq_counter = self.irq_latch;
+            self.irq_pending = true;
+        } else {
+            self.irq_counter += 1;
+        }
+    }
+}
+
+impl Mapper for Vrc4 {
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => write_prg_ram(&mut self.prg_ram, address, value),
+            PRG_ROM_START..=0xFFFF => self.write_register(address, value),
+            _ => {}
+        }
+    }
+
+    fn cpu_peek(&self, address: u16) -> Option<u8> {
+        match address {
+            PRG_RAM_START..=PRG_RAM_END => read_prg_ram(&self.prg_ram, address),
+            PRG_ROM_START..=0xFFFF if !self.prg_rom.is_empty() => {
+                Some(self.prg_rom[self.prg_rom_index(address)])
+            }
+            _ => None,
+        }
+    }
+
+    fn cpu_clock(&mut self, cycles: u8) {
+        if self.irq_control & IRQ_ENABLE == 0 {
+            return;
+        }
+        for _ in 0..cycles {
+            if self.irq_control & IRQ_CYCLE_MODE != 0 {
+                self.clock_irq_counter();
+                continue;
+            }
+            self.irq_prescaler -= IRQ_PRESCALER_STEP;
+            if self.irq_prescaler <= 0 {
+                self.irq_prescaler += IRQ_PRESCALER_RELOAD;
+                self.clock_irq_counter();
+            }
+        }
+    }
+
+    fn ppu_read(&mut self, address: u16) -> u8 {
+        self.chr[self.chr_index(address)]
+    }
+
+    fn ppu_write(&mut self, address: u16, value: u8) {
+        if self.chr_is_ram {
+            let index = self.chr_index(address);
+            self.chr[index] = value;
+        }
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+
+    fn irq(&self) -> bool {
+        self.irq_pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom::{cartridge, numbered_banks};
+
+    fn create_test_vrc(mapper: u16, submapper: u8) -> Vrc4 {
+        Vrc4::new(cartridge(
+            mapper,
+            submapper,
+            numbered_banks(16, PRG_BANK_SIZE),
+            numbered_banks(64, CHR_BANK_SIZE),
+            0,
+        ))
+    }
+
+    #[test]
+    fn test_prg_banks_and_swap_mode() {
+        let mut vrc4 = create_test_vrc(21, 1);
+        vrc4.cpu_write(0x8000, 3);
+        vrc4.cpu_write(0xA000, 5);
+
+        assert_eq!(vrc4.cpu_read(0x8000), 3);
+        assert_eq!(vrc4.cpu_read(0xA000), 5);
+        assert_eq!(vrc4.cpu_read(0xC000), 14);
+        assert_eq!(vrc4.cpu_read(0xE000), 15);
+
+        vrc4.cpu_write(0x9004, PRG_SWAP_MODE);
+        assert_eq!(vrc4.cpu_read(0x8000), 14);
+        assert_eq!(vrc4.cpu_read(0xC000), 3);
+    }
+
+    #[test]
+    fn test_submapper_selects_register_lines() {
+        // The high nibble of CHR bank 1 lives at register 3: A6|A7 on VRC4c,
+        // A2|A3 on VRC4e and A3|A2 swapped on VRC4d
+        for (mapper, submapper, address) in [(21, 2, 0xB0C0), (23, 2, 0xB00C), (25, 2, 0xB00C)] {
+            let mut vrc4 = create_test_vrc(mapper, submapper);
+            vrc4.cpu_write(0xB000, 0x01);
+            vrc4.cpu_write(address, 0x02);
+
+            assert_eq!(vrc4.chr_banks[1], 0x20);
+            assert_eq!(vrc4.ppu_read(0x0000), 1);
+        }
+
+        let mut vrc4d = create_test_vrc(25, 2);
+        vrc4d.cpu_write(0xB004, 0x07);
+        assert_eq!(vrc4d.chr_banks[1], 0x07);
+    }
+
+    #[test]
+    fn test_vrc2a_chr_banks_drop_low_bit() {
+        let mut vrc2 = create_test_vrc(22, 0);
+        vrc2.cpu_write(0xC000, 0x09);
+
+        assert_eq!(vrc2.ppu_read(0x0800), 4);
+
+        // VRC2 has no single-screen modes or IRQ
+        vrc2.cpu_write(0x9000, 0x03);
+        assert_eq!(vrc2.mirroring(), Mirroring::Horizontal);
+        vrc2.cpu_write(0xF002, IRQ_ENABLE | IRQ_CYCLE_MODE);
+        vrc2.cpu_clock(255);
+        assert!(!vrc2.irq());
+    }
+
+    #[test]
+    fn test_vrc4_mirroring_modes() {
+        let mut vrc4 = create_test_vrc(23, 1);
+
+        vrc4.cpu_write(0x9000, 3);
+
+        assert_eq!(vrc4.mirroring(), Mirroring::SingleScreenUpper);
+    }
+
+    #[test]
+    fn test_irq_cycle_mode_and_acknowledge() {
+        let mut vrc4 = create_test_vrc(23, 1);
+        vrc4.cpu_write(0xF000, 0x0C);
+        vrc4.cpu_write(0xF001, 0x0F);
+        vrc4.cpu_write(
+            0xF002,
+            IRQ_ENABLE | IRQ_CYCLE_MODE | IRQ_ENABLE_AFTER_ACKNOWLEDGE,
+        );
+
+        // $FC, $FD, $FE, $FF, then the overflow
+        vrc4.cpu_clock(3);
+        assert!(!vrc4.irq());
+        vrc4.cpu_clock(1);
+        assert!(vrc4.irq());
+
+        vrc4.cpu_write(0xF003, 0);
+        assert!(!vrc4.irq());
+        vrc4.cpu_clock(4);
+        assert!(vrc4.irq());
+    }
+
+    #[test]
+    fn test_irq_scanline_mode_uses_prescaler() {
+        let mut vrc4 = create_test_vrc(23, 1);
+        vrc4.cpu_write(0xF000, 0x0F);
+        vrc4.cpu_write(0xF001, 0x0F);
+        vrc4.cpu_write(0xF002, IRQ_ENABLE);
+
+        // 341 / 3 = 113.67 CPU cycles per scanline
+        vrc4.cpu_clock(113);
+        assert!(!vrc4.irq());
+        vrc4.cpu_clock(1);
+        assert!(vrc4.irq());
+    }
+}
@@ -0,0 +1,536 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::operation_codes::{Operation, OPERATION_TABLE};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+pub const DEFAULT_ORIGIN: u16 = 0x8000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssemblerError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl AssemblerError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        AssemblerError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for AssemblerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AssemblerError {}
+
+#[derive(Debug, Clone)]
+enum Expression {
+    // The flag records a literal written with more than two hex digits, which forces absolute mode
+    Number(u16, bool),
+    Label(String),
+    LowByte(Box<Expression>),
+    HighByte(Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self, labels: &HashMap<String, u16>) -> Option<u16> {
+        match self {
+            Expression::Number(value, _) => Some(*value),
+            Expression::Label(name) => labels.get(name).copied(),
+            Expression::LowByte(inner) => inner.evaluate(labels).map(|value| value & 0xFF),
+            Expression::HighByte(inner) => inner.evaluate(labels).map(|value| value >> 8),
+        }
+    }
+
+    fn is_wide(&self) -> bool {
+        matches!(self, Expression::Number(_, true))
+    }
+}
+
+#[derive(Debug)]
+enum Index {
+    X,
+    Y,
+}
+
+#[derive(Debug)]
+enum OperandSyntax {
+    None,
+    Accumulator,
+    Immediate(Expression),
+    Direct(Expression, Option<Index>),
+    Indirect(Expression),
+    IndexedIndirect(Expression),
+    IndirectIndexed(Expression),
+}
+
+enum StatementKind {
+    Instruction {
+        operation: &'static Operation,
+        operand: Option<Expression>,
+    },
+    Bytes(Vec<Expression>),
+    Words(Vec<Expression>),
+}
+
+struct Statement {
+    line: usize,
+    address: u16,
+    kind: StatementKind,
+}
+
+/// Assembles `source` into bytes starting at the first `.org`, or at $8000 when there is none.
+pub fn assemble(source: &str) -> Result<Vec<u8>, AssemblerError> {
+    let mut labels: HashMap<String, u16> = HashMap::new();
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut origin: Option<u16> = None;
+    let mut address: u32 = DEFAULT_ORIGIN as u32;
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = raw_line.split(';').next().unwrap_or("").trim();
+
+        if let Some((label, rest)) = split_label(text) {
+            if labels.insert(label.to_string(), address as u16).is_some() {
+                return Err(AssemblerError::new(
+                    line,
+                    format!("label {} defined twice", label),
+                ));
+            }
+            text = rest;
+        }
+        if text.is_empty() {
+            continue;
+        }
+
+        let (keyword, argument) = match text.split_once(char::is_whitespace) {
+            Some((keyword, argument)) => (keyword, argument.trim()),
+            None => (text, ""),
+        };
+
+        let kind = match keyword.to_ascii_lowercase().as_str() {
+            ".org" => {
+                let target = parse_expression(argument, line)?
+                    .evaluate(&labels)
+                    .ok_or_else(|| AssemblerError::new(line, ".org needs a known address"))?;
+                if statements.is_empty() && origin.is_none() {
+                    origin = Some(target);
+                } else if (target as u32) < address {
+                    return Err(AssemblerError::new(line, ".org cannot move backwards"));
+                }
+                address = target as u32;
+                continue;
+            }
+            ".byte" => StatementKind::Bytes(parse_list(argument, line)?),
+            ".word" => StatementKind::Words(parse_list(argument, line)?),
+            directive if directive.starts_with('.') => {
+                return Err(AssemblerError::new(
+                    line,
+                    format!("unknown directive {}", keyword),
+                ));
+            }
+            _ => {
+                let syntax = parse_operand(argument, line)?;
+                let (operation, operand) = select_operation(keyword, syntax, &labels, line)?;
+                StatementKind::Instruction { operation, operand }
+            }
+        };
+
+        if origin.is_none() {
+            origin = Some(address as u16);
+        }
+        let size = match &kind {
+            StatementKind::Instruction { operation, .. } => operation.len as u32,
+            StatementKind::Bytes(values) => values.len() as u32,
+            StatementKind::Words(values) => values.len() as u32 * 2,
+        };
+        statements.push(Statement {
+            line,
+            address: address as u16,
+            kind,
+        });
+        address += size;
+        if address > 0x10000 {
+            return Err(AssemblerError::new(line, "program runs past $FFFF"));
+        }
+    }
+
+    let origin = origin.unwrap_or(DEFAULT_ORIGIN);
+    let mut output: Vec<u8> = vec![0; (address - origin as u32) as usize];
+    for statement in &statements {
+        let bytes = encode(statement, &labels)?;
+        let start = (statement.address - origin) as usize;
+        output[start..start + bytes.len()].copy_from_slice(&bytes);
+    }
+    Ok(output)
+}
+
+fn split_label(text: &str) -> Option<(&str, &str)> {
+    let (label, rest) = text.split_once(':')?;
+    let label = label.trim();
+    if is_identifier(label) {
+        Some((label, rest.trim()))
+    } else {
+        None
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_list(argument: &str, line: usize) -> Result<Vec<Expression>, AssemblerError> {
+    argument
+        .split(',')
+        .map(|item| parse_expression(item.trim(), line))
+        .collect()
+}
+
+fn parse_expression(text: &str, line: usize) -> Result<Expression, AssemblerError> {
+    let invalid = || AssemblerError::new(line, format!("invalid expression '{}'", text));
+    if let Some(rest) = text.strip_prefix('<') {
+        return Ok(Expression::LowByte(Box::new(parse_expression(rest, line)?)));
+    }
+    if let Some(rest) = text.strip_prefix('>') {
+        return Ok(Expression::HighByte(Box::new(parse_expression(
+            rest, line,
+        )?)));
+    }
+    if let Some(hex) = text.strip_prefix('$') {
+        let value = u16::from_str_radix(hex, 16).map_err(|_| invalid())?;
+        return Ok(Expression::Number(value, hex.len() > 2));
+    }
+    if let Some(binary) = text.strip_prefix('%') {
+        let value = u16::from_str_radix(binary, 2).map_err(|_| invalid())?;
+        return Ok(Expression::Number(value, binary.len() > 8));
+    }
+    if text.starts_with(|c: char| c.is_ascii_digit()) {
+        let value: u16 = text.parse().map_err(|_| invalid())?;
+        return Ok(Expression::Number(value, value > 0xFF));
+    }
+    if is_identifier(text) {
+        return Ok(Expression::Label(text.to_string()));
+    }
+    Err(invalid())
+}
+
+fn parse_operand(argument: &str, line: usize) -> Result<OperandSyntax, AssemblerError> {
+    let text: String = argument.chars().filter(|c| !c.is_whitespace()).collect();
+    let upper = text.to_ascii_uppercase();
+
+    if text.is_empty() {
+        return Ok(OperandSyntax::None);
+    }
+    if upper == "A" {
+        return Ok(OperandSyntax::Accumulator);
+    }
+    if let Some(value) = text.strip_prefix('#') {
+        return Ok(OperandSyntax::Immediate(parse_expression(value, line)?));
+    }
+    if text.starts_with('(') {
+        if upper.ends_with(",X)") {
+            let inner = &text[1..text.len() - 3];
+            return Ok(OperandSyntax::IndexedIndirect(parse_expression(
+                inner, line,
+            )?));
+        }
+        if upper.ends_with("),Y") {
+            let inner = &text[1..text.len() - 3];
+            return Ok(OperandSyntax::IndirectIndexed(parse_expression(
+                inner, line,
+            )?));
+        }
+        if text.ends_with(')') {
+            let inner = &text[1..text.len() - 1];
+            return Ok(OperandSyntax::Indirect(parse_expression(inner, line)?));
+        }
+        return Err(AssemblerError::new(
+            line,
+            format!("invalid operand '{}'", argument),
+        ));
+    }
+    if upper.ends_with(",X") {
+        let value = parse_expression(&text[..text.len() - 2], line)?;
+        return Ok(OperandSyntax::Direct(value, Some(Index::X)));
+    }
+    if upper.ends_with(",Y") {
+        let value = parse_expression(&text[..text.len() - 2], line)?;
+        return Ok(OperandSyntax::Direct(value, Some(Index::Y)));
+    }
+    Ok(OperandSyntax::Direct(parse_expression(&text, line)?, None))
+}
+
+// Official encodings win over unofficial duplicates such as $EB SBC or the extra NOPs
+fn find_operation(mnemonic: &str, modes: &[AddressingMode]) -> Option<&'static Operation> {
+    let candidates = || {
+        OPERATION_TABLE.iter().flatten().filter(|entry| {
+            entry.operation_name.mnemonic() == mnemonic
+                && modes.contains(&entry.operation.addressing_mode)
+        })
+    };
+    candidates()
+        .find(|entry| !entry.operation_name.is_unofficial())
+        .or_else(|| candidates().next())
+        .map(|entry| entry.operation)
+}
+
+fn select_operation(
+    keyword: &str,
+    syntax: OperandSyntax,
+    labels: &HashMap<String, u16>,
+    line: usize,
+) -> Result<(&'static Operation, Option<Expression>), AssemblerError> {
+    let mnemonic = keyword.to_ascii_uppercase();
+    if !OPERATION_TABLE
+        .iter()
+        .flatten()
+        .any(|entry| entry.operation_name.mnemonic() == mnemonic)
+    {
+        return Err(AssemblerError::new(
+            line,
+            format!("unknown mnemonic {}", keyword),
+        ));
+    }
+    let find = |modes: &[AddressingMode]| find_operation(&mnemonic, modes);
+
+    let (operation, operand) = match syntax {
+        OperandSyntax::None => (
+            find(&[AddressingMode::Implied, AddressingMode::NoneAddressing])
+                .or_else(|| find(&[AddressingMode::Accumulator])),
+            None,
+        ),
+        OperandSyntax::Accumulator => (find(&[AddressingMode::Accumulator]), None),
+        OperandSyntax::Immediate(value) => (find(&[AddressingMode::Immediate]), Some(value)),
+        OperandSyntax::Indirect(value) => (find(&[AddressingMode::Indirect]), Some(value)),
+        OperandSyntax::IndexedIndirect(value) => (find(&[AddressingMode::Indirect_X]), Some(value)),
+        OperandSyntax::IndirectIndexed(value) => (find(&[AddressingMode::Indirect_Y]), Some(value)),
+        OperandSyntax::Direct(value, index) => {
+            let (zero_page, absolute) = match index {
+                None => (AddressingMode::ZeroPage, AddressingMode::Absolute),
+                Some(Index::X) => (AddressingMode::ZeroPage_X, AddressingMode::Absolute_X),
+                Some(Index::Y) => (AddressingMode::ZeroPage_Y, AddressingMode::Absolute_Y),
+            };
+            // Forward references are not known yet, so they get the absolute form
+            let fits_zero_page = !value.is_wide()
+                && matches!(value.evaluate(labels), Some(address) if address <= 0xFF);
+            let relative = match index {
+                None => find(&[AddressingMode::Relative]),
+                Some(_) => None,
+            };
+            let operation = relative.or_else(|| {
+                if fits_zero_page {
+                    find(&[zero_page]).or_else(|| find(&[absolute]))
+                } else {
+                    find(&[absolute]).or_else(|| find(&[zero_page]))
+                }
+            });
+            (operation, Some(value))
+        }
+    };
+
+    let operation = operation.ok_or_else(|| {
+        AssemblerError::new(
+            line,
+            format!("{} does not support this addressing mode", mnemonic),
+        )
+    })?;
+    Ok((operation, operand))
+}
+
+fn resolve(
+    value: &Expression,
+    labels: &HashMap<String, u16>,
+    line: usize,
+) -> Result<u16, AssemblerError> {
+    value.evaluate(labels).ok_or_else(|| match value {
+        Expression::Label(name) => AssemblerError::new(line, format!("undefined label {}", name)),
+        _ => AssemblerError::new(line, "undefined label"),
+    })
+}
+
+fn resolve_byte(
+    value: &Expression,
+    labels: &HashMap<String, u16>,
+    line: usize,
+) -> Result<u8, AssemblerError> {
+    let resolved = resolve(value, labels, line)?;
+    u8::try_from(resolved)
+        .map_err(|_| AssemblerError::new(line, format!("${:X} does not fit in a byte", resolved)))
+}
+
+fn encode(statement: &Statement, labels: &HashMap<String, u16>) -> Result<Vec<u8>, AssemblerError> {
+    let line = statement.line;
+    match &statement.kind {
+        StatementKind::Bytes(values) => values
+            .iter()
+            .map(|value| resolve_byte(value, labels, line))
+            .collect(),
+        StatementKind::Words(values) => {
+            let mut bytes = Vec::with_capacity(values.len() * 2);
+            for value in values {
+                bytes.extend_from_slice(&resolve(value, labels, line)?.to_le_bytes());
+            }
+            Ok(bytes)
+        }
+        StatementKind::Instruction { operation, operand } => {
+            let mut bytes = vec![operation.operation_code];
+            let operand = match operand {
+                Some(operand) => operand,
+                None => return Ok(bytes),
+            };
+            match operation.len {
+                3 => bytes.extend_from_slice(&resolve(operand, labels, line)?.to_le_bytes()),
+                _ if operation.addressing_mode == AddressingMode::Relative => {
+                    let target = resolve(operand, labels, line)? as i32;
+                    let offset = target - (statement.address as i32 + 2);
+                    let offset = i8::try_from(offset).map_err(|_| {
+                        AssemblerError::new(
+                            line,
+                            format!("branch target ${:04X} is out of range", target),
+                        )
+                    })?;
+                    bytes.push(offset as u8);
+                }
+                _ => bytes.push(resolve_byte(operand, labels, line)?),
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::cpu_model::CPU;
+    use crate::cpu::disassembler::disassemble;
+
+    #[test]
+    fn test_assemble_addressing_modes() {
+        let source = "
+            LDA #$FF
+            LDA $20
+            LDA $20,X
+            LDX $20,Y
+            LDA $1234
+            LDA $1234,X
+            LDA $1234,Y
+            LDA ($20,X)
+            LDA ($20),Y
+            JMP ($0200)
+            ASL A
+            ASL
+            TAX
+        ";
+        assert_eq!(
+            assemble(source).unwrap(),
+            vec![
+                0xa9, 0xff, 0xa5, 0x20, 0xb5, 0x20, 0xb6, 0x20, 0xad, 0x34, 0x12, 0xbd, 0x34, 0x12,
+                0xb9, 0x34, 0x12, 0xa1, 0x20, 0xb1, 0x20, 0x6c, 0x00, 0x02, 0x0a, 0x0a, 0xaa,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assemble_labels_and_forward_references() {
+        let source = "
+            .org $C000
+            start:  LDX #$08
+            loop:   DEX
+                    BNE loop      ; backward branch
+                    BEQ done
+                    JMP start
+            done:   JSR routine
+            routine: RTS
+        ";
+        assert_eq!(
+            assemble(source).unwrap(),
+            vec![
+                0xa2, 0x08, 0xca, 0xd0, 0xfd, 0xf0, 0x03, 0x4c, 0x00, 0xc0, 0x20, 0x0d, 0xc0, 0x60,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assemble_data_directives() {
+        let source = "
+            .org $8000
+            table: .byte $01, 2, %11, <table, >table
+                   .word table, $BEEF
+            .org $8010
+                   .byte $FF
+        ";
+        let bytes = assemble(source).unwrap();
+        assert_eq!(bytes.len(), 0x11);
+        assert_eq!(
+            &bytes[..9],
+            &[0x01, 0x02, 0x03, 0x00, 0x80, 0x00, 0x80, 0xef, 0xbe]
+        );
+        assert_eq!(bytes[0x10], 0xff);
+    }
+
+    #[test]
+    fn test_assemble_prefers_official_encodings() {
+        assert_eq!(assemble("NOP\nSBC #$01").unwrap(), vec![0xea, 0xe9, 0x01]);
+        assert_eq!(assemble("DCP $10").unwrap(), vec![0xc7, 0x10]);
+    }
+
+    #[test]
+    fn test_assemble_round_trips_through_disassembler() {
+        let source = "LDA ($20),Y\nSTA $0200,X\nBNE $8000\nJMP ($1234)";
+        let bytes = assemble(source).unwrap();
+        let text: Vec<String> = disassemble(&bytes, DEFAULT_ORIGIN)
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+        assert_eq!(
+            text,
+            vec!["LDA ($20),Y", "STA $0200,X", "BNE $8000", "JMP ($1234)"]
+        );
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        assert_eq!(assemble("FOO #$01").unwrap_err().line, 1);
+        assert!(assemble("LDA ($1234),Y").is_err());
+        assert!(assemble("\nJMP nowhere")
+            .unwrap_err()
+            .message
+            .contains("undefined label nowhere"));
+        assert!(assemble("STA #$01").is_err());
+        assert!(assemble("a: NOP\na: NOP").is_err());
+        assert!(assemble("LDA #$100").is_err());
+        assert!(assemble("loop: NOP\n.org $8100\nBNE loop").is_err());
+    }
+
+    #[test]
+    fn test_assembled_program_runs() {
+        let program = assemble(
+            "
+                LDX #$00
+            loop:
+                INX
+                CPX #$05
+                BNE loop
+                STX $10
+                BRK
+            ",
+        )
+        .unwrap();
+        let mut cpu = CPU::new();
+        cpu.main(program).unwrap();
+
+        assert_eq!(cpu.bus.read(0x10), 0x05);
+    }
+}
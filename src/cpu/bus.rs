@@ -0,0 +1,46 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+
+/// Everything the CPU can address: RAM, memory-mapped registers and cartridges.
+pub trait Bus {
+    fn read(&mut self, address: u16) -> u8;
+
+    fn write(&mut self, address: u16, value: u8);
+
+    /// The unmodified value a read-modify-write instruction writes back one cycle
+    /// before its result. It lands like any other write unless a device, such as
+    /// MMC1, needs to tell the two apart.
+    fn dummy_write(&mut self, address: u16, value: u8) {
+        self.write(address, value);
+    }
+
+    /// Reads without side effects, for debuggers and trace logs. Buses that cannot
+    /// guarantee that (e.g. read-to-clear registers) return `None`.
+    fn peek(&self, _address: u16) -> Option<u8> {
+        None
+    }
+
+    /// Called after every instruction or interrupt with the CPU cycles it took.
+    fn tick(&mut self, _cycles: u8) {}
+
+    /// Whether a device on the bus, such as a cartridge IRQ counter, is holding IRQ low.
+    fn irq(&self) -> bool {
+        false
+    }
+
+    /// Writes battery-backed cartridge RAM to its save file, for hosts shutting down
+    /// or saving on request. Buses without one have nothing to do.
+    fn flush_save(&self) -> Result<(), CartridgeError> {
+        Ok(())
+    }
+
+    fn read_u16(&mut self, address: u16) -> u16 {
+        let lo = self.read(address) as u16;
+        let hi = self.read(address.wrapping_add(1)) as u16;
+        (hi << 8) | lo
+    }
+
+    fn write_u16(&mut self, address: u16, data: u16) {
+        self.write(address, (data & 0xff) as u8);
+        self.write(address.wrapping_add(1), (data >> 8) as u8);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use std::error::Error;
+use std::fmt;
+
+/// Errors raised while decoding or executing an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuError {
+    UnknownOperationCode {
+        program_counter: u16,
+        operation_code: u8,
+    },
+    UnsupportedAddressingMode(AddressingMode),
+    Halted,
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuError::UnknownOperationCode {
+                program_counter,
+                operation_code,
+            } => write!(
+                f,
+                "OperationCode {:x} is not recognized at {:04x}",
+                operation_code, program_counter
+            ),
+            CpuError::UnsupportedAddressingMode(mode) => {
+                write!(f, "mode {:?} is not supported", mode)
+            }
+            CpuError::Halted => write!(f, "CPU is halted"),
+        }
+    }
+}
+
+impl Error for CpuError {}
@@ -1,6 +1,7 @@
 use crate::cpu::addressing_mode::AddressingMode;
 use crate::cpu::bitwise_operation::BitwiseOperation;
-use crate::cpu::cpu_model::CPU;
+use crate::cpu::cpu_error::CpuError;
+use crate::cpu::cpu_model::{CPU, IRQ_VECTOR, STACK};
 use crate::cpu::status_bit::StatusBit;
 // Function helpers
 
@@ -18,39 +19,44 @@ pub fn update_zero_and_negative_flags(cpu: &mut CPU, result: u8) {
     }
 }
 
-pub fn get_operand_address(cpu: &mut CPU, mode: &AddressingMode) -> u16 {
-    match mode {
+pub fn get_operand_address(cpu: &mut CPU, mode: &AddressingMode) -> Result<u16, CpuError> {
+    resolve_operand_address(cpu, mode).map(|(address, _)| address)
+}
+
+// The effective address, and whether indexing carried it onto another page
+fn resolve_operand_address(cpu: &mut CPU, mode: &AddressingMode) -> Result<(u16, bool), CpuError> {
+    let address = match mode {
         AddressingMode::Accumulator => cpu.register_a as u16,
 
         AddressingMode::Immediate => cpu.program_counter,
 
         AddressingMode::Implied => cpu.program_counter, // TODO: Fix
 
-        AddressingMode::ZeroPage => cpu.memory.memory[cpu.program_counter as usize] as u16,
+        AddressingMode::ZeroPage => cpu.bus.read(cpu.program_counter) as u16,
 
-        AddressingMode::Absolute => cpu.memory.read_u16(cpu.program_counter),
+        AddressingMode::Absolute => cpu.bus.read_u16(cpu.program_counter),
 
         AddressingMode::ZeroPage_X => {
-            let pos = cpu.memory.memory[cpu.program_counter as usize];
+            let pos = cpu.bus.read(cpu.program_counter);
             pos.wrapping_add(cpu.register_x) as u16
         }
         AddressingMode::ZeroPage_Y => {
-            let pos = cpu.memory.memory[cpu.program_counter as usize];
+            let pos = cpu.bus.read(cpu.program_counter);
             pos.wrapping_add(cpu.register_y) as u16
         }
 
         AddressingMode::Absolute_X => {
-            let base = cpu.memory.read_u16(cpu.program_counter);
-            base.wrapping_add(cpu.register_x as u16)
+            let base = cpu.bus.read_u16(cpu.program_counter);
+            return Ok(indexed(base, cpu.register_x));
         }
         AddressingMode::Absolute_Y => {
-            let base = cpu.memory.read_u16(cpu.program_counter);
-            base.wrapping_add(cpu.register_y as u16)
+            let base = cpu.bus.read_u16(cpu.program_counter);
+            return Ok(indexed(base, cpu.register_y));
         }
 
         AddressingMode::Indirect => {
-            let base = cpu.memory.read_u16(cpu.program_counter);
-            let lo = cpu.memory.memory[base as usize];
+            let base = cpu.bus.read_u16(cpu.program_counter);
+            let lo = cpu.bus.read(base);
             let hi_addr = if (base & 0xFF) == 0xFF {
                 // Bug: Wrap around within the same page instead of crossing page boundary
                 (base & 0xFF00) | ((base + 1) & 0xFF)
@@ -58,34 +64,56 @@ pub fn get_operand_address(cpu: &mut CPU, mode: &AddressingMode) -> u16 {
                 // Normal case: Fetch from the next sequential address
                 base.wrapping_add(1)
             };
-            let hi: u8 = cpu.memory.memory[hi_addr as usize];
+            let hi: u8 = cpu.bus.read(hi_addr);
             ((hi as u16) << 8) | (lo as u16)
         }
         AddressingMode::Indirect_X => {
-            let base = cpu.memory.memory[cpu.program_counter as usize];
+            let base = cpu.bus.read(cpu.program_counter);
 
             let ptr: u8 = base.wrapping_add(cpu.register_x);
-            let lo = cpu.memory.memory[ptr as usize];
-            let hi = cpu.memory.memory[ptr.wrapping_add(1) as usize];
+            let lo = cpu.bus.read(ptr as u16);
+            let hi = cpu.bus.read(ptr.wrapping_add(1) as u16);
             ((hi as u16) << 8) | (lo as u16)
         }
         AddressingMode::Indirect_Y => {
-            let base = cpu.memory.memory[cpu.program_counter as usize];
+            let base = cpu.bus.read(cpu.program_counter);
 
-            let lo = cpu.memory.memory[base as usize];
-            let hi = cpu.memory.memory[base.wrapping_add(1) as usize];
+            let lo = cpu.bus.read(base as u16);
+            let hi = cpu.bus.read(base.wrapping_add(1) as u16);
             let deref_base = ((hi as u16) << 8) | (lo as u16);
-            deref_base.wrapping_add(cpu.register_y as u16)
+            return Ok(indexed(deref_base, cpu.register_y));
         }
         AddressingMode::Relative => {
-            let offset = cpu.memory.memory[cpu.program_counter as usize] as i8;
-            (cpu.program_counter.wrapping_add(1) as i16 + offset as i16) as u16
+            let offset = cpu.bus.read(cpu.program_counter) as i8;
+            cpu.program_counter
+                .wrapping_add(1)
+                .wrapping_add(offset as u16)
         }
         AddressingMode::NoneAddressing => {
-            panic!("mode {:?} is not supported", mode);
+            return Err(CpuError::UnsupportedAddressingMode(*mode));
         }
+    };
+    Ok((address, false))
+}
+
+fn indexed(base: u16, index: u8) -> (u16, bool) {
+    let address = base.wrapping_add(index as u16);
+    (address, page_crossed(base, address))
+}
+
+fn page_crossed(base: u16, address: u16) -> bool {
+    (base & 0xFF00) != (address & 0xFF00)
+}
+
+// Indexed reads take one more cycle when the effective address lands on another page
+fn get_read_address(cpu: &mut CPU, mode: &AddressingMode) -> Result<u16, CpuError> {
+    let (address, crossed) = resolve_operand_address(cpu, mode)?;
+    if crossed {
+        cpu.extra_cycles += 1;
     }
+    Ok(address)
 }
+
 fn get_bit(current_byte: u8, status_bit: StatusBit) -> u8 {
     (current_byte >> (status_bit as u8)) & 1
 }
@@ -104,10 +132,51 @@ fn update_status_bit(cpu: &mut CPU, position: StatusBit, op: BitwiseOperation) {
         BitwiseOperation::Flip => cpu.status ^= 1 << (position as u8),
     }
 }
-fn compare(cpu: &mut CPU, mode: &AddressingMode, value_to_compare: u8) {
-    let address = get_operand_address(cpu, mode);
-    let value: u8 = cpu.memory.memory[address as usize];
+fn push_to_stack(cpu: &mut CPU, value: u8) {
+    cpu.bus.write(STACK + cpu.stack_pointer as u16, value);
+    cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
+}
 
+fn push_u16_to_stack(cpu: &mut CPU, value: u16) {
+    push_to_stack(cpu, (value >> 8) as u8);
+    push_to_stack(cpu, (value & 0xFF) as u8);
+}
+
+// Shared by BRK, IRQ and NMI: only the vector and the pushed B flag differ
+pub fn service_interrupt(cpu: &mut CPU, vector: u16, break_flag: bool) {
+    push_u16_to_stack(cpu, cpu.program_counter);
+    let mut status = cpu.status | (1 << StatusBit::Unused as u8);
+    if break_flag {
+        status |= 1 << StatusBit::Break as u8;
+    } else {
+        status &= !(1 << StatusBit::Break as u8);
+    }
+    push_to_stack(cpu, status);
+    update_status_bit(cpu, StatusBit::Interrupt, BitwiseOperation::Set);
+    cpu.program_counter = cpu.bus.read_u16(vector);
+}
+
+// B only exists on the stack copy of the status register and bit 5 always reads as set
+fn restore_status(cpu: &mut CPU, status: u8) {
+    cpu.status = (status & !(1 << StatusBit::Break as u8)) | (1 << StatusBit::Unused as u8);
+}
+
+// Read-modify-write instructions write the unmodified value back one cycle before
+// the result, which mappers such as MMC1 can observe. The `*_value` helpers
+// below return the result so the unofficial combinations can use it directly.
+fn write_modified(cpu: &mut CPU, address: u16, original: u8, value: u8) {
+    cpu.bus.dummy_write(address, original);
+    cpu.bus.write(address, value);
+}
+
+fn compare(cpu: &mut CPU, mode: &AddressingMode, value_to_compare: u8) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let value: u8 = cpu.bus.read(address);
+    compare_values(cpu, value_to_compare, value);
+    Ok(())
+}
+
+fn compare_values(cpu: &mut CPU, value_to_compare: u8, value: u8) {
     if value_to_compare >= value {
         update_status_bit(cpu, StatusBit::Carry, BitwiseOperation::Set);
     } else {
@@ -151,222 +220,283 @@ fn adding_with_carry(cpu: &mut CPU, value_to_add: u8) {
     update_zero_and_negative_flags(cpu, cpu.register_a);
 }
 
-pub fn increment_memory(cpu: &mut CPU, _mode: &AddressingMode) {
-    cpu.register_a = cpu.register_a.wrapping_add(1);
-    update_zero_and_negative_flags(cpu, cpu.register_a);
+pub fn increment_memory(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    increment_value(cpu, mode)?;
+    Ok(())
 }
-pub fn increment_x_register(cpu: &mut CPU, _mode: &AddressingMode) {
+
+fn increment_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let value = original.wrapping_add(1);
+    write_modified(cpu, address, original, value);
+    update_zero_and_negative_flags(cpu, value);
+    Ok(value)
+}
+pub fn increment_x_register(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_x = cpu.register_x.wrapping_add(1);
     update_zero_and_negative_flags(cpu, cpu.register_x);
+    Ok(())
 }
-pub fn increment_y_register(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn increment_y_register(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_y = cpu.register_y.wrapping_add(1);
     update_zero_and_negative_flags(cpu, cpu.register_y);
+    Ok(())
 }
 
-pub fn jump(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
+pub fn jump(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, mode)?;
     cpu.program_counter = address;
+    Ok(())
 }
 
-pub fn jump_to_subroutine(cpu: &mut CPU, mode: &AddressingMode) {
-    let address: u16 = get_operand_address(cpu, mode);
+pub fn jump_to_subroutine(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address: u16 = get_operand_address(cpu, mode)?;
     let return_address: u16 = cpu.program_counter + 1;
     let high: u8 = (return_address >> 8) as u8;
     let low: u8 = (return_address & 0xFF) as u8;
-    cpu.memory.memory[(0x0100 + cpu.stack_pointer as u16) as usize] = high;
+    cpu.bus.write(0x0100 + cpu.stack_pointer as u16, high);
     cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
-    cpu.memory.memory[(0x0100 + cpu.stack_pointer as u16) as usize] = low;
+    cpu.bus.write(0x0100 + cpu.stack_pointer as u16, low);
     cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
     cpu.program_counter = address;
+    Ok(())
 }
 
-pub fn decrement_memory(cpu: &mut CPU, _mode: &AddressingMode) {
-    cpu.register_a = cpu.register_a.wrapping_sub(1);
-    update_zero_and_negative_flags(cpu, cpu.register_a);
+pub fn decrement_memory(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    decrement_value(cpu, mode)?;
+    Ok(())
+}
+
+fn decrement_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let value = original.wrapping_sub(1);
+    write_modified(cpu, address, original, value);
+    update_zero_and_negative_flags(cpu, value);
+    Ok(value)
 }
-pub fn decrement_x_register(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn decrement_x_register(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_x = cpu.register_x.wrapping_sub(1);
     update_zero_and_negative_flags(cpu, cpu.register_x);
+    Ok(())
 }
-pub fn decrement_y_register(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn decrement_y_register(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_y = cpu.register_y.wrapping_sub(1);
     update_zero_and_negative_flags(cpu, cpu.register_y);
+    Ok(())
 }
 
-fn branch(cpu: &mut CPU, mode: &AddressingMode, condition: bool) {
+fn branch(cpu: &mut CPU, mode: &AddressingMode, condition: bool) -> Result<(), CpuError> {
     if condition {
-        let target_address = get_operand_address(cpu, mode);
+        let target_address = get_operand_address(cpu, mode)?;
+        let next_address = cpu.program_counter.wrapping_add(1);
+        cpu.extra_cycles += 1;
+        if page_crossed(next_address, target_address) {
+            cpu.extra_cycles += 1;
+        }
         cpu.program_counter = target_address;
     }
+    Ok(())
 }
 
-pub fn branch_if_carry_clear(cpu: &mut CPU, mode: &AddressingMode) {
-    branch(cpu, mode, cpu.status & (1 << (StatusBit::Carry as u8)) == 0);
+pub fn branch_if_carry_clear(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    branch(cpu, mode, cpu.status & (1 << (StatusBit::Carry as u8)) == 0)
 }
 
-pub fn branch_if_carry_set(cpu: &mut CPU, mode: &AddressingMode) {
-    branch(cpu, mode, cpu.status & (1 << (StatusBit::Carry as u8)) != 0);
+pub fn branch_if_carry_set(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    branch(cpu, mode, cpu.status & (1 << (StatusBit::Carry as u8)) != 0)
 }
 
-pub fn branch_if_equal(cpu: &mut CPU, mode: &AddressingMode) {
-    branch(cpu, mode, cpu.status & (1 << (StatusBit::Zero as u8)) != 0);
+pub fn branch_if_equal(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    branch(cpu, mode, cpu.status & (1 << (StatusBit::Zero as u8)) != 0)
 }
 
-pub fn branch_if_minus(cpu: &mut CPU, mode: &AddressingMode) {
+pub fn branch_if_minus(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
     branch(
         cpu,
         mode,
         cpu.status & (1 << (StatusBit::Negative as u8)) != 0,
-    );
+    )
 }
 
-pub fn branch_if_not_equal(cpu: &mut CPU, mode: &AddressingMode) {
-    branch(cpu, mode, cpu.status & (1 << (StatusBit::Zero as u8)) == 0);
+pub fn branch_if_not_equal(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    branch(cpu, mode, cpu.status & (1 << (StatusBit::Zero as u8)) == 0)
 }
 
-pub fn branch_if_positive(cpu: &mut CPU, mode: &AddressingMode) {
+pub fn branch_if_positive(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
     branch(
         cpu,
         mode,
         cpu.status & (1 << (StatusBit::Negative as u8)) == 0,
-    );
+    )
 }
 
-pub fn branch_if_overflow_clear(cpu: &mut CPU, mode: &AddressingMode) {
+pub fn branch_if_overflow_clear(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
     branch(
         cpu,
         mode,
         cpu.status & (1 << (StatusBit::Overflow as u8)) == 0,
-    );
+    )
 }
 
-pub fn branch_if_overflow_set(cpu: &mut CPU, mode: &AddressingMode) {
+pub fn branch_if_overflow_set(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
     branch(
         cpu,
         mode,
         cpu.status & (1 << (StatusBit::Overflow as u8)) != 0,
-    );
+    )
 }
 
-pub fn load_accumulator(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    let value: u8 = cpu.memory.memory[address as usize];
+pub fn load_accumulator(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let value: u8 = cpu.bus.read(address);
     cpu.register_a = value;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
-pub fn load_x_register(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    let value: u8 = cpu.memory.memory[address as usize];
+pub fn load_x_register(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let value: u8 = cpu.bus.read(address);
     cpu.register_x = value;
     update_zero_and_negative_flags(cpu, cpu.register_x);
+    Ok(())
 }
-pub fn load_y_register(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    let value: u8 = cpu.memory.memory[address as usize];
+pub fn load_y_register(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let value: u8 = cpu.bus.read(address);
     cpu.register_y = value;
     update_zero_and_negative_flags(cpu, cpu.register_y);
+    Ok(())
 }
 
-pub fn transfer_accumulator_to_x(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_accumulator_to_x(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_x = cpu.register_a;
     update_zero_and_negative_flags(cpu, cpu.register_x);
+    Ok(())
 }
-pub fn transfer_accumulator_to_y(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_accumulator_to_y(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_y = cpu.register_a;
     update_zero_and_negative_flags(cpu, cpu.register_y);
+    Ok(())
 }
 
-pub fn store_accumulator(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    cpu.memory.memory[address as usize] = cpu.register_a;
+pub fn store_accumulator(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    cpu.bus.write(address, cpu.register_a);
+    Ok(())
 }
-pub fn store_x_register(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    cpu.memory.memory[address as usize] = cpu.register_x;
+pub fn store_x_register(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    cpu.bus.write(address, cpu.register_x);
+    Ok(())
 }
-pub fn store_y_register(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    cpu.memory.memory[address as usize] = cpu.register_y;
+pub fn store_y_register(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    cpu.bus.write(address, cpu.register_y);
+    Ok(())
 }
-pub fn compare_a(cpu: &mut CPU, mode: &AddressingMode) {
-    compare(cpu, mode, cpu.register_a);
+pub fn compare_a(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    compare(cpu, mode, cpu.register_a)
 }
-pub fn compare_x(cpu: &mut CPU, mode: &AddressingMode) {
-    compare(cpu, mode, cpu.register_x);
+pub fn compare_x(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    compare(cpu, mode, cpu.register_x)
 }
-pub fn compare_y(cpu: &mut CPU, mode: &AddressingMode) {
-    compare(cpu, mode, cpu.register_y);
+pub fn compare_y(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    compare(cpu, mode, cpu.register_y)
 }
 
-pub fn add_with_carry(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    let result: u8 = cpu.memory.memory[address as usize];
+pub fn add_with_carry(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let result: u8 = cpu.bus.read(address);
     adding_with_carry(cpu, result);
+    Ok(())
 }
 
-pub fn substract_with_carry(cpu: &mut CPU, mode: &AddressingMode) {
-    let address = get_operand_address(cpu, mode);
-    let result: u8 = cpu.memory.memory[address as usize];
+pub fn substract_with_carry(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, mode)?;
+    let result: u8 = cpu.bus.read(address);
     adding_with_carry(cpu, !result);
+    Ok(())
 }
 
-pub fn transfer_stack_pointer_to_x(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_stack_pointer_to_x(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_x = cpu.stack_pointer;
     update_zero_and_negative_flags(cpu, cpu.register_x);
+    Ok(())
 }
 
-pub fn transfer_x_to_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_x_to_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_a = cpu.register_x;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn transfer_y_to_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_y_to_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.register_a = cpu.register_y;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn transfer_x_to_stack_pointer(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn transfer_x_to_stack_pointer(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.stack_pointer = cpu.register_x;
+    Ok(())
 }
 
-pub fn return_from_interrupt(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn return_from_interrupt(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
-    let status = cpu.memory.memory[0x0100 + cpu.stack_pointer as usize];
+    let status = cpu.bus.read(0x0100 + cpu.stack_pointer as u16);
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
-    let lo = cpu.memory.memory[0x0100 + cpu.stack_pointer as usize] as u16;
+    let lo = cpu.bus.read(0x0100 + cpu.stack_pointer as u16) as u16;
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
-    let hi = cpu.memory.memory[0x0100 + cpu.stack_pointer as usize] as u16;
+    let hi = cpu.bus.read(0x0100 + cpu.stack_pointer as u16) as u16;
     cpu.program_counter = (hi << 8) | lo;
-    cpu.status = status;
+    restore_status(cpu, status);
+    Ok(())
 }
 
-pub fn return_from_subroutine(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn return_from_subroutine(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
-    let lo = cpu.memory.memory[0x0100 + cpu.stack_pointer as usize] as u16;
+    let lo = cpu.bus.read(0x0100 + cpu.stack_pointer as u16) as u16;
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
-    let hi = cpu.memory.memory[0x0100 + cpu.stack_pointer as usize] as u16;
+    let hi = cpu.bus.read(0x0100 + cpu.stack_pointer as u16) as u16;
     cpu.program_counter = (hi << 8) | lo;
     cpu.program_counter = cpu.program_counter.wrapping_add(1);
+    Ok(())
+}
+
+pub fn force_interruptions(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    // BRK is followed by a padding byte, so the return address skips it
+    cpu.program_counter = cpu.program_counter.wrapping_add(1);
+    service_interrupt(cpu, IRQ_VECTOR, true);
+    Ok(())
 }
 
-pub fn force_interruptions(_cpu: &mut CPU, _mode: &AddressingMode) {}
+pub fn arithmetic_shift_left(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    shift_left_value(cpu, mode)?;
+    Ok(())
+}
 
-pub fn arithmetic_shift_left(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let mut value = cpu.memory.memory[address as usize];
+fn shift_left_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let mut value = original;
     let carry = value >> 7;
     value <<= 1;
-    cpu.memory.memory[address as usize] = value;
+    write_modified(cpu, address, original, value);
     update_zero_and_negative_flags(cpu, value);
     update_status_bit(
         cpu,
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(value)
 }
 
-pub fn arithmetic_shift_left_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn arithmetic_shift_left_accumulator(
+    cpu: &mut CPU,
+    _mode: &AddressingMode,
+) -> Result<(), CpuError> {
     let mut value = cpu.register_a;
     let carry = value >> 7;
     value <<= 1;
@@ -377,11 +507,12 @@ pub fn arithmetic_shift_left_accumulator(cpu: &mut CPU, _mode: &AddressingMode)
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(())
 }
 
-pub fn bit_test(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let value = cpu.memory.memory[address as usize];
+pub fn bit_test(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, _mode)?;
+    let value = cpu.bus.read(address);
     let result = cpu.register_a & value;
 
     update_status_bit(
@@ -399,60 +530,78 @@ pub fn bit_test(cpu: &mut CPU, _mode: &AddressingMode) {
         StatusBit::Negative,
         BitwiseOperation::from_bool((value >> 7) & 1 == 1),
     );
+    Ok(())
 }
 
-pub fn clear_carry_flag(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn clear_carry_flag(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Carry, BitwiseOperation::Unset);
+    Ok(())
 }
 
-pub fn clear_decimal_mode(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn clear_decimal_mode(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Decimal, BitwiseOperation::Unset);
+    Ok(())
 }
 
-pub fn clear_interrupt_disable(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn clear_interrupt_disable(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Interrupt, BitwiseOperation::Unset);
+    Ok(())
 }
 
-pub fn clear_overflow_flag(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn clear_overflow_flag(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Overflow, BitwiseOperation::Unset);
+    Ok(())
 }
 
-pub fn exclusive_or(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let value = cpu.memory.memory[address as usize];
+pub fn exclusive_or(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, _mode)?;
+    let value = cpu.bus.read(address);
     cpu.register_a ^= value;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn logical_and(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let value = cpu.memory.memory[address as usize];
+pub fn logical_and(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, _mode)?;
+    let value = cpu.bus.read(address);
     cpu.register_a &= value;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn logical_inclusive_or(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let value = cpu.memory.memory[address as usize];
+pub fn logical_inclusive_or(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_read_address(cpu, _mode)?;
+    let value = cpu.bus.read(address);
     cpu.register_a |= value;
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn logical_shift_right(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let mut value = cpu.memory.memory[address as usize];
+pub fn logical_shift_right(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    shift_right_value(cpu, mode)?;
+    Ok(())
+}
+
+fn shift_right_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let mut value = original;
     let carry = value & 1;
     value >>= 1;
-    cpu.memory.memory[address as usize] = value;
+    write_modified(cpu, address, original, value);
     update_zero_and_negative_flags(cpu, value);
     update_status_bit(
         cpu,
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(value)
 }
 
-pub fn logical_shift_right_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn logical_shift_right_accumulator(
+    cpu: &mut CPU,
+    _mode: &AddressingMode,
+) -> Result<(), CpuError> {
     let mut value = cpu.register_a;
     let carry = value & 1;
     value >>= 1;
@@ -463,49 +612,63 @@ pub fn logical_shift_right_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(())
 }
 
-pub fn pull_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn pull_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
     let address = 0x0100 + cpu.stack_pointer as u16;
-    cpu.register_a = cpu.memory.memory[address as usize];
+    cpu.register_a = cpu.bus.read(address);
     update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
 }
 
-pub fn pull_processor_status(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn pull_processor_status(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
     let address = 0x0100 + cpu.stack_pointer as u16;
-    cpu.status = cpu.memory.memory[address as usize];
+    let status = cpu.bus.read(address);
+    restore_status(cpu, status);
+    Ok(())
 }
 
-pub fn push_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn push_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     let address = 0x0100 + cpu.stack_pointer as u16;
-    cpu.memory.memory[address as usize] = cpu.register_a;
+    cpu.bus.write(address, cpu.register_a);
     cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
+    Ok(())
 }
 
-pub fn push_processor_status(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn push_processor_status(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     let address = 0x0100 + cpu.stack_pointer as u16;
-    cpu.memory.memory[address as usize] = cpu.status;
+    let status = cpu.status | (1 << StatusBit::Break as u8) | (1 << StatusBit::Unused as u8);
+    cpu.bus.write(address, status);
     cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
+    Ok(())
+}
+
+pub fn rotate_left(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    rotate_left_value(cpu, mode)?;
+    Ok(())
 }
 
-pub fn rotate_left(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let mut value = cpu.memory.memory[address as usize];
+fn rotate_left_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let mut value = original;
     let carry = value >> 7;
     value <<= 1;
     value |= get_bit(cpu.status, StatusBit::Carry);
-    cpu.memory.memory[address as usize] = value;
+    write_modified(cpu, address, original, value);
     update_zero_and_negative_flags(cpu, value);
     update_status_bit(
         cpu,
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(value)
 }
 
-pub fn rotate_left_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn rotate_left_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     let mut value = cpu.register_a;
     let carry = value >> 7;
     value <<= 1;
@@ -517,24 +680,32 @@ pub fn rotate_left_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(())
 }
 
-pub fn rotate_right(cpu: &mut CPU, _mode: &AddressingMode) {
-    let address = get_operand_address(cpu, _mode);
-    let mut value = cpu.memory.memory[address as usize];
+pub fn rotate_right(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    rotate_right_value(cpu, mode)?;
+    Ok(())
+}
+
+fn rotate_right_value(cpu: &mut CPU, mode: &AddressingMode) -> Result<u8, CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    let original = cpu.bus.read(address);
+    let mut value = original;
     let carry = value & 1;
     value >>= 1;
     value |= get_bit(cpu.status, StatusBit::Carry) << 7;
-    cpu.memory.memory[address as usize] = value;
+    write_modified(cpu, address, original, value);
     update_zero_and_negative_flags(cpu, value);
     update_status_bit(
         cpu,
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(value)
 }
 
-pub fn rotate_right_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn rotate_right_accumulator(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     let mut value = cpu.register_a;
     let carry = value & 1;
     value >>= 1;
@@ -546,26 +717,98 @@ pub fn rotate_right_accumulator(cpu: &mut CPU, _mode: &AddressingMode) {
         StatusBit::Carry,
         BitwiseOperation::from_bool(carry == 1),
     );
+    Ok(())
 }
 
-pub fn set_carry_flag(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn set_carry_flag(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Carry, BitwiseOperation::Set);
+    Ok(())
 }
 
-pub fn set_decimal_flag(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn set_decimal_flag(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Decimal, BitwiseOperation::Set);
+    Ok(())
 }
 
-pub fn set_interrupt_disable(cpu: &mut CPU, _mode: &AddressingMode) {
+pub fn set_interrupt_disable(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
     update_status_bit(cpu, StatusBit::Interrupt, BitwiseOperation::Set);
+    Ok(())
+}
+
+pub fn no_operation(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    // Unofficial NOPs still decode their operand, page-cross cycle included
+    match mode {
+        AddressingMode::Implied | AddressingMode::NoneAddressing => Ok(()),
+        _ => get_read_address(cpu, mode).map(|_| ()),
+    }
 }
+
+// Unofficial operations, built from the official ones they combine
+
+pub fn load_accumulator_and_x(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    load_accumulator(cpu, mode)?;
+    transfer_accumulator_to_x(cpu, mode)
+}
+
+pub fn store_accumulator_and_x(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let address = get_operand_address(cpu, mode)?;
+    cpu.bus.write(address, cpu.register_a & cpu.register_x);
+    Ok(())
+}
+
+pub fn decrement_and_compare(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let value = decrement_value(cpu, mode)?;
+    compare_values(cpu, cpu.register_a, value);
+    Ok(())
+}
+
+pub fn increment_and_substract(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let value = increment_value(cpu, mode)?;
+    adding_with_carry(cpu, !value);
+    Ok(())
+}
+
+pub fn shift_left_and_or(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    cpu.register_a |= shift_left_value(cpu, mode)?;
+    update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
+}
+
+pub fn rotate_left_and_and(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    cpu.register_a &= rotate_left_value(cpu, mode)?;
+    update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
+}
+
+pub fn shift_right_and_exclusive_or(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    cpu.register_a ^= shift_right_value(cpu, mode)?;
+    update_zero_and_negative_flags(cpu, cpu.register_a);
+    Ok(())
+}
+
+pub fn rotate_right_and_add(cpu: &mut CPU, mode: &AddressingMode) -> Result<(), CpuError> {
+    let value = rotate_right_value(cpu, mode)?;
+    adding_with_carry(cpu, value);
+    Ok(())
+}
+
+pub fn jam(cpu: &mut CPU, _mode: &AddressingMode) -> Result<(), CpuError> {
+    // The processor locks up on the JAM opcode until it is reset
+    cpu.program_counter = cpu.program_counter.wrapping_sub(1);
+    cpu.halted = true;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cpu::addressing_mode::AddressingMode;
+    use crate::cpu::bus::Bus;
     use crate::cpu::cpu_functions;
-    use crate::cpu::cpu_model::STACK_RESET;
+    use crate::cpu::cpu_model::{ExecuteFunction, STACK_RESET};
     use crate::cpu::memory::Memory;
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     // Helper function to create a new CPU instance
     const TEST_BASE_REGISTER_A: u8 = 0x05;
@@ -582,7 +825,12 @@ mod tests {
             status: TEST_BASE_STATUS,
             program_counter: TEST_BASE_PROGRAM_COUNTER,
             stack_pointer: STACK_RESET,
-            memory: Memory::new(),
+            bus: Box::new(Memory::new()),
+            cycles: 0,
+            extra_cycles: 0,
+            halted: false,
+            nmi_pending: false,
+            irq_line: false,
         }
     }
     fn get_bit(current_byte: u8, status_bit: StatusBit) -> u8 {
@@ -595,7 +843,7 @@ mod tests {
         let mode: AddressingMode = AddressingMode::Immediate;
 
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             cpu.program_counter
         );
     }
@@ -605,10 +853,13 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data: u16 = 0x80;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
         let mode: AddressingMode = AddressingMode::ZeroPage;
 
-        assert_eq!(cpu_functions::get_operand_address(&mut cpu, &mode), data);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
+            data
+        );
     }
 
     #[test]
@@ -616,11 +867,11 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data: u16 = 0x80;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
         let mode: AddressingMode = AddressingMode::ZeroPage_X;
 
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             data + cpu.register_x as u16
         );
     }
@@ -630,11 +881,11 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data: u16 = 0x80;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
 
         let mode = AddressingMode::ZeroPage_Y;
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             data + cpu.register_y as u16
         );
     }
@@ -643,18 +894,24 @@ mod tests {
     fn test_get_operand_address_relative_positive() {
         let mut cpu: CPU = create_test_cpu();
         cpu.program_counter = 0x2000;
-        cpu.memory.memory[cpu.program_counter as usize] = 0x05;
+        cpu.bus.write(cpu.program_counter, 0x05);
         let mode: AddressingMode = AddressingMode::Relative;
-        assert_eq!(cpu_functions::get_operand_address(&mut cpu, &mode), 0x2006);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
+            0x2006
+        );
     }
 
     #[test]
     fn test_get_operand_address_relative_negative() {
         let mut cpu: CPU = create_test_cpu();
         cpu.program_counter = 0x2000;
-        cpu.memory.memory[cpu.program_counter as usize] = 0xFB;
+        cpu.bus.write(cpu.program_counter, 0xFB);
         let mode: AddressingMode = AddressingMode::Relative;
-        assert_eq!(cpu_functions::get_operand_address(&mut cpu, &mode), 0x1FFC);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
+            0x1FFC
+        );
     }
 
     #[test]
@@ -662,10 +919,13 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data: u16 = 0xC000;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
         let mode = AddressingMode::Absolute;
 
-        assert_eq!(cpu_functions::get_operand_address(&mut cpu, &mode), data);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
+            data
+        );
     }
 
     #[test]
@@ -673,11 +933,11 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data = 0xC000;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
         let mode = AddressingMode::Absolute_X;
 
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             data + cpu.register_x as u16
         );
     }
@@ -687,11 +947,11 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let data = 0xC000;
-        cpu.memory.write_u16(cpu.program_counter, data);
+        cpu.bus.write_u16(cpu.program_counter, data);
         let mode = AddressingMode::Absolute_Y;
 
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             data + cpu.register_y as u16
         );
     }
@@ -702,12 +962,15 @@ mod tests {
 
         let base: u8 = 0x20;
         let ptr: u8 = base.wrapping_add(cpu.register_x);
-        cpu.memory.memory[cpu.program_counter as usize] = base;
-        cpu.memory.memory[ptr as usize] = 0x34;
-        cpu.memory.memory[ptr.wrapping_add(1) as usize] = 0x12;
+        cpu.bus.write(cpu.program_counter, base);
+        cpu.bus.write(ptr as u16, 0x34);
+        cpu.bus.write(ptr.wrapping_add(1) as u16, 0x12);
 
         let mode = AddressingMode::Indirect_X;
-        assert_eq!(cpu_functions::get_operand_address(&mut cpu, &mode), 0x1234);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
+            0x1234
+        );
     }
 
     #[test]
@@ -715,23 +978,27 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
 
         let base: u8 = 0x40;
-        cpu.memory.memory[cpu.program_counter as usize] = base;
-        cpu.memory.memory[base as usize] = 0x78;
-        cpu.memory.memory[base.wrapping_add(1) as usize] = 0x56;
+        cpu.bus.write(cpu.program_counter, base);
+        cpu.bus.write(base as u16, 0x78);
+        cpu.bus.write(base.wrapping_add(1) as u16, 0x56);
 
         let mode = AddressingMode::Indirect_Y;
         assert_eq!(
-            cpu_functions::get_operand_address(&mut cpu, &mode),
+            cpu_functions::get_operand_address(&mut cpu, &mode).unwrap(),
             0x5678 + cpu.register_y as u16
         );
     }
 
     #[test]
-    #[should_panic]
     fn test_get_operand_address_none_addressing() {
         let mut cpu: CPU = create_test_cpu();
         let mode = AddressingMode::NoneAddressing;
-        cpu_functions::get_operand_address(&mut cpu, &mode);
+        assert_eq!(
+            cpu_functions::get_operand_address(&mut cpu, &mode),
+            Err(CpuError::UnsupportedAddressingMode(
+                AddressingMode::NoneAddressing
+            ))
+        );
     }
 
     // Tests for the functions themselves
@@ -741,10 +1008,16 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
+        cpu.bus
+            .write(TEST_BASE_PROGRAM_COUNTER, TEST_BASE_REGISTER_A);
         for _ in 0..AMOUNT {
-            increment_memory(&mut cpu, &mode);
+            increment_memory(&mut cpu, &mode).unwrap();
         }
-        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A.wrapping_add(AMOUNT));
+        assert_eq!(
+            cpu.bus.read(TEST_BASE_PROGRAM_COUNTER),
+            TEST_BASE_REGISTER_A.wrapping_add(AMOUNT)
+        );
+        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A);
     }
 
     #[test]
@@ -753,7 +1026,7 @@ mod tests {
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
         for _ in 0..AMOUNT {
-            increment_x_register(&mut cpu, &mode);
+            increment_x_register(&mut cpu, &mode).unwrap();
         }
         assert_eq!(cpu.register_x, TEST_BASE_REGISTER_X.wrapping_add(AMOUNT));
     }
@@ -764,7 +1037,7 @@ mod tests {
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
         for _ in 0..AMOUNT {
-            increment_y_register(&mut cpu, &mode);
+            increment_y_register(&mut cpu, &mode).unwrap();
         }
         assert_eq!(cpu.register_y, TEST_BASE_REGISTER_Y.wrapping_add(AMOUNT));
     }
@@ -773,10 +1046,10 @@ mod tests {
     fn test_jump_absolute_normal() {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[0x1000] = 0x34;
-        cpu.memory.memory[0x1001] = 0x12;
+        cpu.bus.write(0x1000, 0x34);
+        cpu.bus.write(0x1001, 0x12);
 
-        jump(&mut cpu, &AddressingMode::Absolute);
+        jump(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0x1234);
     }
@@ -785,10 +1058,10 @@ mod tests {
     fn test_jump_absolute_max_address() {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x2000;
-        cpu.memory.memory[0x2000] = 0xFF;
-        cpu.memory.memory[0x2001] = 0xFF;
+        cpu.bus.write(0x2000, 0xFF);
+        cpu.bus.write(0x2001, 0xFF);
 
-        jump(&mut cpu, &AddressingMode::Absolute);
+        jump(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0xFFFF);
     }
@@ -797,12 +1070,12 @@ mod tests {
     fn test_jump_indirect_normal() {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x3000;
-        cpu.memory.memory[0x3000] = 0x50;
-        cpu.memory.memory[0x3001] = 0x40;
-        cpu.memory.memory[0x4050] = 0x78;
-        cpu.memory.memory[0x4051] = 0x56;
+        cpu.bus.write(0x3000, 0x50);
+        cpu.bus.write(0x3001, 0x40);
+        cpu.bus.write(0x4050, 0x78);
+        cpu.bus.write(0x4051, 0x56);
 
-        jump(&mut cpu, &AddressingMode::Indirect);
+        jump(&mut cpu, &AddressingMode::Indirect).unwrap();
 
         assert_eq!(cpu.program_counter, 0x5678);
     }
@@ -811,13 +1084,13 @@ mod tests {
     fn test_jump_indirect_page_boundary_bug() {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x4000;
-        cpu.memory.memory[0x4000] = 0xFF;
-        cpu.memory.memory[0x4001] = 0x01;
-        cpu.memory.memory[0x01FF] = 0xCD;
-        cpu.memory.memory[0x0200] = 0xAB;
-        cpu.memory.memory[0x0100] = 0xEF;
+        cpu.bus.write(0x4000, 0xFF);
+        cpu.bus.write(0x4001, 0x01);
+        cpu.bus.write(0x01FF, 0xCD);
+        cpu.bus.write(0x0200, 0xAB);
+        cpu.bus.write(0x0100, 0xEF);
 
-        jump(&mut cpu, &AddressingMode::Indirect);
+        jump(&mut cpu, &AddressingMode::Indirect).unwrap();
 
         assert_eq!(cpu.program_counter, 0xEFCD);
     }
@@ -827,15 +1100,15 @@ mod tests {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x1001;
         cpu.stack_pointer = 0xFF;
-        cpu.memory.memory[0x1001] = 0x34;
-        cpu.memory.memory[0x1002] = 0x12;
+        cpu.bus.write(0x1001, 0x34);
+        cpu.bus.write(0x1002, 0x12);
 
-        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute);
+        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0x1234);
         assert_eq!(cpu.stack_pointer, 0xFD);
-        assert_eq!(cpu.memory.memory[0x01FF], 0x10);
-        assert_eq!(cpu.memory.memory[0x01FE], 0x02);
+        assert_eq!(cpu.bus.read(0x01FF), 0x10);
+        assert_eq!(cpu.bus.read(0x01FE), 0x02);
     }
 
     #[test]
@@ -843,15 +1116,15 @@ mod tests {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x2000;
         cpu.stack_pointer = 0x01;
-        cpu.memory.memory[0x2000] = 0x56;
-        cpu.memory.memory[0x2001] = 0x34;
+        cpu.bus.write(0x2000, 0x56);
+        cpu.bus.write(0x2001, 0x34);
 
-        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute);
+        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0x3456);
         assert_eq!(cpu.stack_pointer, 0xFF);
-        assert_eq!(cpu.memory.memory[0x0101], 0x20);
-        assert_eq!(cpu.memory.memory[0x0100], 0x01);
+        assert_eq!(cpu.bus.read(0x0101), 0x20);
+        assert_eq!(cpu.bus.read(0x0100), 0x01);
     }
 
     #[test]
@@ -859,15 +1132,15 @@ mod tests {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x3000;
         cpu.stack_pointer = 0xFF;
-        cpu.memory.memory[0x3000] = 0x00;
-        cpu.memory.memory[0x3001] = 0x00;
+        cpu.bus.write(0x3000, 0x00);
+        cpu.bus.write(0x3001, 0x00);
 
-        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute);
+        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0x0000);
         assert_eq!(cpu.stack_pointer, 0xFD);
-        assert_eq!(cpu.memory.memory[0x01FF], 0x30);
-        assert_eq!(cpu.memory.memory[0x01FE], 0x01);
+        assert_eq!(cpu.bus.read(0x01FF), 0x30);
+        assert_eq!(cpu.bus.read(0x01FE), 0x01);
     }
 
     #[test]
@@ -875,15 +1148,15 @@ mod tests {
         let mut cpu = CPU::new();
         cpu.program_counter = 0x4000;
         cpu.stack_pointer = 0xFF;
-        cpu.memory.memory[0x4000] = 0xFF;
-        cpu.memory.memory[0x4001] = 0xFF;
+        cpu.bus.write(0x4000, 0xFF);
+        cpu.bus.write(0x4001, 0xFF);
 
-        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute);
+        jump_to_subroutine(&mut cpu, &AddressingMode::Absolute).unwrap();
 
         assert_eq!(cpu.program_counter, 0xFFFF);
         assert_eq!(cpu.stack_pointer, 0xFD);
-        assert_eq!(cpu.memory.memory[0x01FF], 0x40);
-        assert_eq!(cpu.memory.memory[0x01FE], 0x01);
+        assert_eq!(cpu.bus.read(0x01FF), 0x40);
+        assert_eq!(cpu.bus.read(0x01FE), 0x01);
     }
 
     #[test]
@@ -891,10 +1164,16 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
+        cpu.bus
+            .write(TEST_BASE_PROGRAM_COUNTER, TEST_BASE_REGISTER_A);
         for _ in 0..AMOUNT {
-            decrement_memory(&mut cpu, &mode);
+            decrement_memory(&mut cpu, &mode).unwrap();
         }
-        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A.wrapping_sub(AMOUNT));
+        assert_eq!(
+            cpu.bus.read(TEST_BASE_PROGRAM_COUNTER),
+            TEST_BASE_REGISTER_A.wrapping_sub(AMOUNT)
+        );
+        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A);
     }
 
     #[test]
@@ -903,7 +1182,7 @@ mod tests {
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
         for _ in 0..AMOUNT {
-            decrement_x_register(&mut cpu, &mode);
+            decrement_x_register(&mut cpu, &mode).unwrap();
         }
         assert_eq!(cpu.register_x, TEST_BASE_REGISTER_X.wrapping_sub(AMOUNT));
     }
@@ -914,7 +1193,7 @@ mod tests {
         let mode: AddressingMode = AddressingMode::Immediate;
         const AMOUNT: u8 = 20;
         for _ in 0..AMOUNT {
-            decrement_y_register(&mut cpu, &mode);
+            decrement_y_register(&mut cpu, &mode).unwrap();
         }
         assert_eq!(cpu.register_y, TEST_BASE_REGISTER_Y.wrapping_sub(AMOUNT));
     }
@@ -922,61 +1201,61 @@ mod tests {
     #[test]
     fn test_load_accumulator() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory
+        cpu.bus
             .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
 
         let data_to_load: u8 = 0xff;
-        cpu.memory.memory[SAFE_MEMORY_ADDRESS as usize] = data_to_load;
+        cpu.bus.write(SAFE_MEMORY_ADDRESS, data_to_load);
 
-        load_accumulator(&mut cpu, &AddressingMode::Absolute);
+        load_accumulator(&mut cpu, &AddressingMode::Absolute).unwrap();
         assert_eq!(cpu.register_a, data_to_load);
 
-        load_x_register(&mut cpu, &AddressingMode::Absolute);
+        load_x_register(&mut cpu, &AddressingMode::Absolute).unwrap();
         assert_eq!(cpu.register_x, data_to_load);
 
-        load_y_register(&mut cpu, &AddressingMode::Absolute);
+        load_y_register(&mut cpu, &AddressingMode::Absolute).unwrap();
         assert_eq!(cpu.register_y, data_to_load);
     }
 
     #[test]
     fn test_transfer_accumulator_to_x() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_accumulator_to_x(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_accumulator_to_x(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.register_x, TEST_BASE_REGISTER_A);
     }
 
     #[test]
     fn test_transfer_accumulator_to_y() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_accumulator_to_y(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_accumulator_to_y(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.register_y, TEST_BASE_REGISTER_A);
     }
 
     #[test]
     fn test_transfer_x_to_accumulator() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_x_to_accumulator(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_x_to_accumulator(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.register_a, TEST_BASE_REGISTER_X);
     }
 
     #[test]
     fn test_transfer_y_to_accumulator() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_y_to_accumulator(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_y_to_accumulator(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.register_a, TEST_BASE_REGISTER_Y);
     }
 
     #[test]
     fn test_transfer_stack_pointer_to_x() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_stack_pointer_to_x(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_stack_pointer_to_x(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.register_x, STACK_RESET);
     }
 
     #[test]
     fn test_transfer_x_to_stack_pointer() {
         let mut cpu: CPU = create_test_cpu();
-        transfer_x_to_stack_pointer(&mut cpu, &AddressingMode::NoneAddressing);
+        transfer_x_to_stack_pointer(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.stack_pointer, TEST_BASE_REGISTER_X);
     }
 
@@ -986,10 +1265,10 @@ mod tests {
 
         let return_addr = 0xABCD;
         cpu.stack_pointer = 0xFD;
-        cpu.memory.memory[0x01FE] = ((return_addr - 1) & 0xFF) as u8;
-        cpu.memory.memory[0x01FF] = ((return_addr - 1) >> 8) as u8;
+        cpu.bus.write(0x01FE, ((return_addr - 1) & 0xFF) as u8);
+        cpu.bus.write(0x01FF, ((return_addr - 1) >> 8) as u8);
 
-        return_from_subroutine(&mut cpu, &AddressingMode::Implied);
+        return_from_subroutine(&mut cpu, &AddressingMode::Implied).unwrap();
 
         assert_eq!(
             cpu.program_counter, return_addr,
@@ -1006,66 +1285,113 @@ mod tests {
         let mut cpu = CPU::new();
 
         cpu.stack_pointer = 0xFC;
-        cpu.memory.memory[0x01FD] = 0x55;
-        cpu.memory.memory[0x01FE] = 0xCD;
-        cpu.memory.memory[0x01FF] = 0xAB;
+        cpu.bus.write(0x01FD, 0x55);
+        cpu.bus.write(0x01FE, 0xCD);
+        cpu.bus.write(0x01FF, 0xAB);
 
-        return_from_interrupt(&mut cpu, &AddressingMode::Implied);
+        return_from_interrupt(&mut cpu, &AddressingMode::Implied).unwrap();
 
         assert_eq!(
             cpu.program_counter, 0xABCD,
             "RTI should set PC to popped address"
         );
-        assert_eq!(cpu.status, 0x55, "RTI should restore status register");
+        assert_eq!(
+            cpu.status, 0x65,
+            "RTI should restore status register with B cleared and bit 5 set"
+        );
         assert_eq!(
             cpu.stack_pointer, 0xFF,
             "RTI should increment SP by 3 from initial value"
         );
     }
 
+    #[test]
+    fn test_push_and_pull_processor_status() {
+        let mut cpu = CPU::new();
+        cpu.status = 0b1100_0011;
+
+        push_processor_status(&mut cpu, &AddressingMode::Implied).unwrap();
+        assert_eq!(cpu.bus.read(0x01FD), 0b1111_0011);
+
+        cpu.status = 0;
+        pull_processor_status(&mut cpu, &AddressingMode::Implied).unwrap();
+        assert_eq!(cpu.status, 0b1110_0011);
+        assert_eq!(cpu.stack_pointer, STACK_RESET);
+    }
+
+    #[test]
+    fn test_force_interruptions() {
+        let mut cpu = CPU::new();
+        cpu.program_counter = 0x8001;
+        cpu.status = 0b0000_0001;
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+
+        force_interruptions(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
+
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(cpu.stack_pointer, STACK_RESET.wrapping_sub(3));
+        assert_eq!(cpu.bus.read(0x01FD), 0x80);
+        assert_eq!(cpu.bus.read(0x01FC), 0x02);
+        assert_eq!(cpu.bus.read(0x01FB), 0b0011_0001);
+        assert_eq!(get_bit(cpu.status, StatusBit::Interrupt), 1);
+    }
+
+    #[test]
+    fn test_force_interruptions_return_from_interrupt() {
+        let mut cpu = CPU::new();
+        cpu.program_counter = 0x8001;
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+
+        force_interruptions(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
+        return_from_interrupt(&mut cpu, &AddressingMode::Implied).unwrap();
+
+        assert_eq!(cpu.program_counter, 0x8002);
+        assert_eq!(cpu.stack_pointer, STACK_RESET);
+    }
+
     #[test]
     fn test_store_accumulator() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory
+        cpu.bus
             .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
-        store_accumulator(&mut cpu, &AddressingMode::Absolute);
-        let value_stored = cpu.memory.memory[SAFE_MEMORY_ADDRESS as usize];
+        store_accumulator(&mut cpu, &AddressingMode::Absolute).unwrap();
+        let value_stored = cpu.bus.read(SAFE_MEMORY_ADDRESS);
         assert_eq!(value_stored, TEST_BASE_REGISTER_A);
     }
 
     #[test]
     fn test_store_x_register() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory
+        cpu.bus
             .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
-        store_x_register(&mut cpu, &AddressingMode::Absolute);
-        let value_stored = cpu.memory.memory[SAFE_MEMORY_ADDRESS as usize];
+        store_x_register(&mut cpu, &AddressingMode::Absolute).unwrap();
+        let value_stored = cpu.bus.read(SAFE_MEMORY_ADDRESS);
         assert_eq!(value_stored, TEST_BASE_REGISTER_X);
     }
 
     #[test]
     fn test_store_y_register() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory
+        cpu.bus
             .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
-        store_y_register(&mut cpu, &AddressingMode::Absolute);
-        let value_stored = cpu.memory.memory[SAFE_MEMORY_ADDRESS as usize];
+        store_y_register(&mut cpu, &AddressingMode::Absolute).unwrap();
+        let value_stored = cpu.bus.read(SAFE_MEMORY_ADDRESS);
         assert_eq!(value_stored, TEST_BASE_REGISTER_Y);
     }
 
     #[test]
     fn test_compare_equal() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory.memory[TEST_BASE_PROGRAM_COUNTER as usize] = 0x40;
-        compare(&mut cpu, &AddressingMode::Immediate, 0x40);
+        cpu.bus.write(TEST_BASE_PROGRAM_COUNTER, 0x40);
+        compare(&mut cpu, &AddressingMode::Immediate, 0x40).unwrap();
         let status: u8 = cpu.status;
         assert_eq!(status, 0x03);
     }
     #[test]
     fn test_compare_lesser() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory.memory[TEST_BASE_PROGRAM_COUNTER as usize] = 0xff;
-        compare(&mut cpu, &AddressingMode::Immediate, 0x01);
+        cpu.bus.write(TEST_BASE_PROGRAM_COUNTER, 0xff);
+        compare(&mut cpu, &AddressingMode::Immediate, 0x01).unwrap();
         let status: u8 = cpu.status;
         assert_eq!(status, 0x00);
     }
@@ -1073,8 +1399,8 @@ mod tests {
     #[test]
     fn test_compare_greater() {
         let mut cpu: CPU = create_test_cpu();
-        cpu.memory.memory[TEST_BASE_PROGRAM_COUNTER as usize] = 0x80;
-        compare(&mut cpu, &AddressingMode::Immediate, 0x7f);
+        cpu.bus.write(TEST_BASE_PROGRAM_COUNTER, 0x80);
+        compare(&mut cpu, &AddressingMode::Immediate, 0x7f).unwrap();
         let status: u8 = cpu.status;
         assert_eq!(status, 0x80);
     }
@@ -1132,8 +1458,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1145,8 +1471,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x01;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1157,8 +1483,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x01;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_carry_set(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_carry_set(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1170,8 +1496,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_carry_set(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_carry_set(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1182,8 +1508,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x02;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_equal(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_equal(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1195,8 +1521,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_equal(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_equal(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1207,8 +1533,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x80;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_minus(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_minus(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1220,8 +1546,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_minus(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_minus(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1232,8 +1558,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_not_equal(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_not_equal(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1245,8 +1571,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x02;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_not_equal(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_not_equal(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1257,8 +1583,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_positive(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_positive(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1270,8 +1596,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x80;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_positive(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_positive(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1282,8 +1608,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_overflow_clear(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_overflow_clear(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1295,11 +1621,48 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x40;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_overflow_clear(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_overflow_clear(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
+    #[test]
+    fn test_branch_taken_adds_cycle() {
+        let mut cpu: CPU = create_test_cpu();
+        cpu.program_counter = 0x1000;
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative).unwrap();
+        assert_eq!(cpu.extra_cycles, 1);
+    }
+
+    #[test]
+    fn test_branch_taken_across_page_adds_two_cycles() {
+        let mut cpu: CPU = create_test_cpu();
+        cpu.program_counter = 0x1000;
+        cpu.bus.write(cpu.program_counter, 0xF0);
+        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative).unwrap();
+        assert_eq!(cpu.program_counter, 0x0FF1);
+        assert_eq!(cpu.extra_cycles, 2);
+    }
+
+    #[test]
+    fn test_branch_not_taken_adds_no_cycles() {
+        let mut cpu: CPU = create_test_cpu();
+        cpu.status = 0x01;
+        cpu.program_counter = 0x1000;
+        cpu.bus.write(cpu.program_counter, 0xF0);
+        cpu_functions::branch_if_carry_clear(&mut cpu, &AddressingMode::Relative).unwrap();
+        assert_eq!(cpu.extra_cycles, 0);
+    }
+
+    #[test]
+    fn test_load_accumulator_page_cross_penalty() {
+        let mut cpu: CPU = create_test_cpu();
+        cpu.bus.write_u16(TEST_BASE_PROGRAM_COUNTER, 0x02FF);
+        load_accumulator(&mut cpu, &AddressingMode::Absolute_X).unwrap();
+        assert_eq!(cpu.extra_cycles, 1);
+    }
+
     // --- Tests for branch_if_overflow_set ---
 
     #[test]
@@ -1307,8 +1670,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x40;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_overflow_set(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_overflow_set(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(
             cpu.program_counter,
             0x1000u16.wrapping_add(1).wrapping_add(5)
@@ -1320,8 +1683,8 @@ mod tests {
         let mut cpu: CPU = create_test_cpu();
         cpu.status = 0x00;
         cpu.program_counter = 0x1000;
-        cpu.memory.memory[cpu.program_counter as usize] = 5;
-        cpu_functions::branch_if_overflow_set(&mut cpu, &AddressingMode::Relative);
+        cpu.bus.write(cpu.program_counter, 5);
+        cpu_functions::branch_if_overflow_set(&mut cpu, &AddressingMode::Relative).unwrap();
         assert_eq!(cpu.program_counter, 0x1000);
     }
 
@@ -1330,13 +1693,14 @@ mod tests {
         for testing_parameters in generate_tests_add_with_carry() {
             let mut cpu: CPU = create_test_cpu();
             cpu.register_a = testing_parameters.0;
-            cpu.memory.memory[TEST_BASE_PROGRAM_COUNTER as usize] = testing_parameters.1;
+            cpu.bus
+                .write(TEST_BASE_PROGRAM_COUNTER, testing_parameters.1);
             if testing_parameters.2 {
                 update_status_bit(&mut cpu, StatusBit::Carry, BitwiseOperation::Set);
             } else {
                 update_status_bit(&mut cpu, StatusBit::Carry, BitwiseOperation::Unset);
             }
-            add_with_carry(&mut cpu, &AddressingMode::Immediate);
+            add_with_carry(&mut cpu, &AddressingMode::Immediate).unwrap();
             // Summing 0x60 + 0x50
             assert_eq!(cpu.register_a, testing_parameters.3);
             assert_eq!(get_bit(cpu.status, StatusBit::Carry), testing_parameters.4);
@@ -1357,13 +1721,14 @@ mod tests {
         for testing_parameters in generate_tests_substract_with_carry() {
             let mut cpu: CPU = create_test_cpu();
             cpu.register_a = testing_parameters.0;
-            cpu.memory.memory[TEST_BASE_PROGRAM_COUNTER as usize] = testing_parameters.1;
+            cpu.bus
+                .write(TEST_BASE_PROGRAM_COUNTER, testing_parameters.1);
             if testing_parameters.2 {
                 update_status_bit(&mut cpu, StatusBit::Carry, BitwiseOperation::Set);
             } else {
                 update_status_bit(&mut cpu, StatusBit::Carry, BitwiseOperation::Unset);
             }
-            substract_with_carry(&mut cpu, &AddressingMode::Immediate);
+            substract_with_carry(&mut cpu, &AddressingMode::Immediate).unwrap();
             // Summing 0x60 + 0x50
             assert_eq!(cpu.register_a, testing_parameters.3);
             assert_eq!(get_bit(cpu.status, StatusBit::Carry), testing_parameters.4);
@@ -1382,9 +1747,9 @@ mod tests {
     #[test]
     fn test_arithmetic_shift_left() {
         let mut cpu = create_test_cpu();
-        cpu.memory.memory[cpu.program_counter as usize] = 0b0100_0001;
-        arithmetic_shift_left(&mut cpu, &AddressingMode::Immediate);
-        assert_eq!(cpu.memory.memory[cpu.program_counter as usize], 0b1000_0010);
+        cpu.bus.write(cpu.program_counter, 0b0100_0001);
+        arithmetic_shift_left(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b1000_0010);
         assert!(cpu.status & (StatusBit::Carry as u8) == 0);
         assert!(cpu.status & (StatusBit::Negative as u8) == 0);
         assert!(cpu.status & (StatusBit::Zero as u8) == 0);
@@ -1394,7 +1759,7 @@ mod tests {
     fn test_arithmetic_shift_left_acumulator() {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b0100_0001;
-        arithmetic_shift_left_accumulator(&mut cpu, &AddressingMode::Accumulator);
+        arithmetic_shift_left_accumulator(&mut cpu, &AddressingMode::Accumulator).unwrap();
         assert_eq!(cpu.register_a, 0b1000_0010);
         assert!(cpu.status & (StatusBit::Carry as u8) == 0);
         assert!(cpu.status & (StatusBit::Negative as u8) == 0);
@@ -1406,10 +1771,10 @@ mod tests {
         let mut cpu = create_test_cpu();
 
         cpu.register_a = 0b0000_0101;
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1100_0000;
+        cpu.bus.write(cpu.program_counter, 0b1100_0000);
         let mode = AddressingMode::Immediate;
 
-        bit_test(&mut cpu, &mode);
+        bit_test(&mut cpu, &mode).unwrap();
         assert_eq!(get_bit(cpu.status, StatusBit::Zero), 1);
         assert_eq!(get_bit(cpu.status, StatusBit::Overflow), 1);
         assert_eq!(get_bit(cpu.status, StatusBit::Negative), 1);
@@ -1419,7 +1784,7 @@ mod tests {
     fn test_clear_carry_flag() {
         let mut cpu = create_test_cpu();
         cpu.status = 0b0000_0001;
-        clear_carry_flag(&mut cpu, &AddressingMode::NoneAddressing);
+        clear_carry_flag(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
         assert_eq!(cpu.status, 0b0000_0000);
     }
 
@@ -1427,8 +1792,8 @@ mod tests {
     fn test_exclusive_or() {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b1010_1010;
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1100_1100;
-        exclusive_or(&mut cpu, &AddressingMode::Immediate);
+        cpu.bus.write(cpu.program_counter, 0b1100_1100);
+        exclusive_or(&mut cpu, &AddressingMode::Immediate).unwrap();
         assert_eq!(cpu.register_a, 0b0110_0110);
     }
 
@@ -1436,8 +1801,8 @@ mod tests {
     fn test_logical_and() {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b1010_1010;
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1100_1100;
-        logical_and(&mut cpu, &AddressingMode::Immediate);
+        cpu.bus.write(cpu.program_counter, 0b1100_1100);
+        logical_and(&mut cpu, &AddressingMode::Immediate).unwrap();
         assert_eq!(cpu.register_a, 0b1000_1000);
     }
 
@@ -1445,17 +1810,17 @@ mod tests {
     fn test_logical_inclusive_or() {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b1010_1010;
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1100_1100;
-        logical_inclusive_or(&mut cpu, &AddressingMode::Immediate);
+        cpu.bus.write(cpu.program_counter, 0b1100_1100);
+        logical_inclusive_or(&mut cpu, &AddressingMode::Immediate).unwrap();
         assert_eq!(cpu.register_a, 0b1110_1110);
     }
 
     #[test]
     fn test_logical_shift_right() {
         let mut cpu = create_test_cpu();
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1000_0001;
-        logical_shift_right(&mut cpu, &AddressingMode::Immediate);
-        assert_eq!(cpu.memory.memory[cpu.program_counter as usize], 0b0100_0000);
+        cpu.bus.write(cpu.program_counter, 0b1000_0001);
+        logical_shift_right(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b0100_0000);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
         assert_eq!(get_bit(cpu.status, StatusBit::Zero), 0);
         assert_eq!(get_bit(cpu.status, StatusBit::Negative), 0);
@@ -1465,7 +1830,7 @@ mod tests {
     fn test_logical_shift_right_accumulator() {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b1000_0001;
-        logical_shift_right_accumulator(&mut cpu, &AddressingMode::Accumulator);
+        logical_shift_right_accumulator(&mut cpu, &AddressingMode::Accumulator).unwrap();
         assert_eq!(cpu.register_a, 0b0100_0000);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
         assert_eq!(get_bit(cpu.status, StatusBit::Zero), 0);
@@ -1475,10 +1840,10 @@ mod tests {
     #[test]
     fn test_rotate_left() {
         let mut cpu = create_test_cpu();
-        cpu.memory.memory[cpu.program_counter as usize] = 0b1000_0001;
+        cpu.bus.write(cpu.program_counter, 0b1000_0001);
         cpu.status = 0b0000_0001;
-        rotate_left(&mut cpu, &AddressingMode::Immediate);
-        assert_eq!(cpu.memory.memory[cpu.program_counter as usize], 0b0000_0011);
+        rotate_left(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b0000_0011);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
     }
 
@@ -1487,7 +1852,7 @@ mod tests {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b1000_0001;
         cpu.status = 0b0000_0001;
-        rotate_left_accumulator(&mut cpu, &AddressingMode::Accumulator);
+        rotate_left_accumulator(&mut cpu, &AddressingMode::Accumulator).unwrap();
         assert_eq!(cpu.register_a, 0b0000_0011);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
     }
@@ -1495,10 +1860,10 @@ mod tests {
     #[test]
     fn test_rotate_right() {
         let mut cpu = create_test_cpu();
-        cpu.memory.memory[cpu.program_counter as usize] = 0b0000_0011;
+        cpu.bus.write(cpu.program_counter, 0b0000_0011);
         cpu.status = 0b0000_0001;
-        rotate_right(&mut cpu, &AddressingMode::Immediate);
-        assert_eq!(cpu.memory.memory[cpu.program_counter as usize], 0b1000_0001);
+        rotate_right(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b1000_0001);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
     }
 
@@ -1507,8 +1872,192 @@ mod tests {
         let mut cpu = create_test_cpu();
         cpu.register_a = 0b0000_0011;
         cpu.status = 0b0000_0001;
-        rotate_right_accumulator(&mut cpu, &AddressingMode::Accumulator);
+        rotate_right_accumulator(&mut cpu, &AddressingMode::Accumulator).unwrap();
         assert_eq!(cpu.register_a, 0b1000_0001);
         assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
     }
+
+    #[test]
+    fn test_no_operation_page_cross_penalty() {
+        let mut cpu = create_test_cpu();
+        cpu.bus.write_u16(TEST_BASE_PROGRAM_COUNTER, 0x02FF);
+        no_operation(&mut cpu, &AddressingMode::Absolute_X).unwrap();
+        assert_eq!(cpu.extra_cycles, 1);
+        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A);
+    }
+
+    #[test]
+    fn test_load_accumulator_and_x() {
+        let mut cpu = create_test_cpu();
+        cpu.bus
+            .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
+        cpu.bus.write(SAFE_MEMORY_ADDRESS, 0x80);
+        load_accumulator_and_x(&mut cpu, &AddressingMode::Absolute).unwrap();
+        assert_eq!(cpu.register_a, 0x80);
+        assert_eq!(cpu.register_x, 0x80);
+        assert_eq!(get_bit(cpu.status, StatusBit::Negative), 1);
+    }
+
+    #[test]
+    fn test_store_accumulator_and_x() {
+        let mut cpu = create_test_cpu();
+        cpu.bus
+            .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
+        cpu.register_a = 0b1100_1100;
+        cpu.register_x = 0b1010_1010;
+        store_accumulator_and_x(&mut cpu, &AddressingMode::Absolute).unwrap();
+        assert_eq!(cpu.bus.read(SAFE_MEMORY_ADDRESS), 0b1000_1000);
+        assert_eq!(cpu.status, TEST_BASE_STATUS);
+    }
+
+    #[test]
+    fn test_decrement_and_compare() {
+        let mut cpu = create_test_cpu();
+        cpu.bus
+            .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
+        cpu.bus.write(SAFE_MEMORY_ADDRESS, TEST_BASE_REGISTER_A + 1);
+        decrement_and_compare(&mut cpu, &AddressingMode::Absolute).unwrap();
+        assert_eq!(cpu.bus.read(SAFE_MEMORY_ADDRESS), TEST_BASE_REGISTER_A);
+        assert_eq!(get_bit(cpu.status, StatusBit::Zero), 1);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
+    }
+
+    #[test]
+    fn test_increment_and_substract() {
+        let mut cpu = create_test_cpu();
+        cpu.bus
+            .write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
+        cpu.bus.write(SAFE_MEMORY_ADDRESS, 0x01);
+        cpu.status = 0b0000_0001;
+        increment_and_substract(&mut cpu, &AddressingMode::Absolute).unwrap();
+        assert_eq!(cpu.bus.read(SAFE_MEMORY_ADDRESS), 0x02);
+        assert_eq!(cpu.register_a, TEST_BASE_REGISTER_A - 2);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
+    }
+
+    #[test]
+    fn test_shift_left_and_or() {
+        let mut cpu = create_test_cpu();
+        cpu.register_a = 0b0000_0001;
+        cpu.bus.write(cpu.program_counter, 0b1100_0000);
+        shift_left_and_or(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b1000_0000);
+        assert_eq!(cpu.register_a, 0b1000_0001);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
+        assert_eq!(get_bit(cpu.status, StatusBit::Negative), 1);
+    }
+
+    #[test]
+    fn test_rotate_left_and_and() {
+        let mut cpu = create_test_cpu();
+        cpu.register_a = 0b0000_0011;
+        cpu.status = 0b0000_0001;
+        cpu.bus.write(cpu.program_counter, 0b1000_0001);
+        rotate_left_and_and(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b0000_0011);
+        assert_eq!(cpu.register_a, 0b0000_0011);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
+    }
+
+    #[test]
+    fn test_shift_right_and_exclusive_or() {
+        let mut cpu = create_test_cpu();
+        cpu.register_a = 0b0000_1111;
+        cpu.bus.write(cpu.program_counter, 0b0000_0011);
+        shift_right_and_exclusive_or(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b0000_0001);
+        assert_eq!(cpu.register_a, 0b0000_1110);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 1);
+    }
+
+    #[test]
+    fn test_rotate_right_and_add() {
+        let mut cpu = create_test_cpu();
+        cpu.register_a = 0x10;
+        cpu.bus.write(cpu.program_counter, 0b0000_0101);
+        rotate_right_and_add(&mut cpu, &AddressingMode::Immediate).unwrap();
+        assert_eq!(cpu.bus.read(cpu.program_counter), 0b0000_0010);
+        // The carry shifted out of the rotation is added too
+        assert_eq!(cpu.register_a, 0x13);
+        assert_eq!(get_bit(cpu.status, StatusBit::Carry), 0);
+    }
+
+    // Counts reads of one address so tests can catch operands being re-read
+    struct ReadCountingBus {
+        memory: Memory,
+        address: u16,
+        reads: Rc<Cell<usize>>,
+    }
+
+    impl Bus for ReadCountingBus {
+        fn read(&mut self, address: u16) -> u8 {
+            if address == self.address {
+                self.reads.set(self.reads.get() + 1);
+            }
+            self.memory.read(address)
+        }
+
+        fn write(&mut self, address: u16, value: u8) {
+            self.memory.write(address, value);
+        }
+    }
+
+    #[test]
+    fn test_unofficial_read_modify_write_reads_operand_once() {
+        let operations: [ExecuteFunction; 6] = [
+            decrement_and_compare,
+            increment_and_substract,
+            shift_left_and_or,
+            rotate_left_and_and,
+            shift_right_and_exclusive_or,
+            rotate_right_and_add,
+        ];
+        for operation in operations {
+            let reads = Rc::new(Cell::new(0));
+            let mut memory = Memory::new();
+            memory.write_u16(TEST_BASE_PROGRAM_COUNTER, SAFE_MEMORY_ADDRESS);
+            let mut cpu = create_test_cpu();
+            cpu.bus = Box::new(ReadCountingBus {
+                memory,
+                address: SAFE_MEMORY_ADDRESS,
+                reads: Rc::clone(&reads),
+            });
+            operation(&mut cpu, &AddressingMode::Absolute).unwrap();
+            assert_eq!(reads.get(), 1);
+        }
+    }
+
+    #[test]
+    fn test_page_cross_penalty_does_not_reread_operands() {
+        for (mode, watched) in [
+            (AddressingMode::Absolute_X, TEST_BASE_PROGRAM_COUNTER),
+            (AddressingMode::Indirect_Y, 0x0010),
+        ] {
+            let reads = Rc::new(Cell::new(0));
+            let mut memory = Memory::new();
+            // Both index from $20FF, so X or Y carries them onto page $21
+            memory.write_u16(TEST_BASE_PROGRAM_COUNTER, 0x20FF);
+            if mode == AddressingMode::Indirect_Y {
+                memory.write(TEST_BASE_PROGRAM_COUNTER, 0x10);
+                memory.write_u16(0x0010, 0x20FF);
+            }
+            let mut cpu = create_test_cpu();
+            cpu.bus = Box::new(ReadCountingBus {
+                memory,
+                address: watched,
+                reads: Rc::clone(&reads),
+            });
+            load_accumulator(&mut cpu, &mode).unwrap();
+            assert_eq!(reads.get(), 1);
+            assert_eq!(cpu.extra_cycles, 1);
+        }
+    }
+
+    #[test]
+    fn test_jam() {
+        let mut cpu = create_test_cpu();
+        jam(&mut cpu, &AddressingMode::NoneAddressing).unwrap();
+        assert!(cpu.halted);
+        assert_eq!(cpu.program_counter, TEST_BASE_PROGRAM_COUNTER - 1);
+    }
 }
@@ -1,9 +1,20 @@
 use super::operation_codes;
-use crate::cpu::cpu_model::ExecuteFunction;
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::bus::Bus;
+use crate::cpu::cpu_error::CpuError;
+use crate::cpu::cpu_functions;
 use crate::cpu::cpu_model::CPU;
-use crate::cpu::cpu_model::STACK_RESET;
+use crate::cpu::cpu_model::{IRQ_VECTOR, NMI_VECTOR, RESET_VECTOR, STACK_RESET};
+use crate::cpu::interrupt::Interrupt;
 use crate::cpu::memory::Memory;
-use std::collections::HashMap;
+use crate::cpu::status_bit::StatusBit;
+use crate::cpu::step_result::StepResult;
+
+const BRK_OPERATION_CODE: u8 = 0x00;
+const PROGRAM_START: u16 = 0x8000;
+const RESET_CYCLES: u64 = 7;
+const INTERRUPT_CYCLES: u8 = 7;
 
 impl Default for CPU {
     fn default() -> Self {
@@ -13,6 +24,10 @@ impl Default for CPU {
 
 impl CPU {
     pub fn new() -> Self {
+        Self::with_bus(Box::new(Memory::new()))
+    }
+
+    pub fn with_bus(bus: Box<dyn Bus>) -> Self {
         CPU {
             register_a: 0,
             register_x: 0,
@@ -20,39 +35,480 @@ impl CPU {
             status: 0,
             stack_pointer: STACK_RESET,
             program_counter: 0,
-            memory: Memory::new(),
+            bus,
+            cycles: 0,
+            extra_cycles: 0,
+            halted: false,
+            nmi_pending: false,
+            irq_line: false,
         }
     }
 
-    pub fn run(&mut self) {
-        let operation_codes: &HashMap<u8, (&'static operation_codes::Operation, ExecuteFunction)> =
-            &operation_codes::OPERATION_CODES_MAP;
+    pub fn step(&mut self) -> Result<StepResult, CpuError> {
+        if self.halted {
+            return Err(CpuError::Halted);
+        }
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            return Ok(self.interrupt(Interrupt::Nmi, NMI_VECTOR));
+        }
+        let irq = self.irq_line || self.bus.irq();
+        if irq && self.status & (1 << StatusBit::Interrupt as u8) == 0 {
+            return Ok(self.interrupt(Interrupt::Irq, IRQ_VECTOR));
+        }
+        let code = self.bus.read(self.program_counter);
+        let entry = operation_codes::OPERATION_TABLE[code as usize].ok_or(
+            CpuError::UnknownOperationCode {
+                program_counter: self.program_counter,
+                operation_code: code,
+            },
+        )?;
+        let operation_code = entry.operation;
+        self.program_counter = self.program_counter.wrapping_add(1);
+        let program_counter_previous = self.program_counter;
+        self.extra_cycles = 0;
+        (entry.execute_function)(self, &operation_code.addressing_mode)?;
+
+        if program_counter_previous == self.program_counter {
+            self.program_counter = self
+                .program_counter
+                .wrapping_add((operation_code.len - 1) as u16);
+        }
+
+        let cycles = operation_code.cycles + self.extra_cycles;
+        self.cycles += cycles as u64;
+        self.bus.tick(cycles);
+
+        Ok(StepResult {
+            operation_code: code,
+            addressing_mode: operation_code.addressing_mode,
+            cycles,
+            program_counter: self.program_counter,
+            interrupt: None,
+        })
+    }
+
+    fn interrupt(&mut self, interrupt: Interrupt, vector: u16) -> StepResult {
+        cpu_functions::service_interrupt(self, vector, false);
+        self.cycles += INTERRUPT_CYCLES as u64;
+        self.bus.tick(INTERRUPT_CYCLES);
+
+        StepResult {
+            operation_code: BRK_OPERATION_CODE,
+            addressing_mode: AddressingMode::Implied,
+            cycles: INTERRUPT_CYCLES,
+            program_counter: self.program_counter,
+            interrupt: Some(interrupt),
+        }
+    }
+
+    /// Latches a non-maskable interrupt, serviced before the next instruction.
+    pub fn trigger_nmi(&mut self) {
+        self.nmi_pending = true;
+    }
+
+    /// Drives the level-triggered IRQ line; it is serviced while the Interrupt flag is clear.
+    pub fn set_irq(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    /// Steps the CPU until `stop` returns true for the instruction just executed.
+    pub fn run_until<F>(&mut self, mut stop: F) -> Result<(), CpuError>
+    where
+        F: FnMut(&CPU, &StepResult) -> bool,
+    {
+        loop {
+            let step = self.step()?;
+            if stop(self, &step) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Runs until the CPU traps on an instruction that jumps or branches to itself,
+    /// the way test ROMs signal completion, and returns the trap address.
+    pub fn run_until_trap(&mut self) -> Result<u16, CpuError> {
         loop {
-            let code = self.memory.memory[self.program_counter as usize];
-            self.program_counter += 1;
-            let program_counter_previous = self.program_counter;
-            let (operation_code, execute_function) = operation_codes
-                .get(&code)
-                .unwrap_or_else(|| panic!("OperationCode {:x} is not recognized", code));
-            execute_function(self, &operation_code.addressing_mode);
-
-            if program_counter_previous == self.program_counter {
-                self.program_counter += (operation_code.len - 1) as u16;
+            let address = self.program_counter;
+            let step = self.step()?;
+            if step.interrupt.is_none() && step.program_counter == address {
+                return Ok(address);
             }
         }
     }
 
+    /// Runs the program until it executes a BRK instruction.
+    pub fn run(&mut self) -> Result<(), CpuError> {
+        self.run_until(|_, step| {
+            step.operation_code == BRK_OPERATION_CODE && step.interrupt.is_none()
+        })
+    }
+
+    /// Runs the reset sequence: like an interrupt without the pushes, it masks IRQs
+    /// and moves the stack pointer down three bytes before jumping through $FFFC.
     pub fn reset(&mut self) {
         self.register_a = 0;
         self.register_x = 0;
-        self.status = 0;
+        self.status = (1 << StatusBit::Interrupt as u8) | (1 << StatusBit::Unused as u8);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(3);
+        self.halted = false;
+        // The reset sequence takes 7 cycles before the first instruction is fetched
+        self.cycles = RESET_CYCLES;
+
+        self.program_counter = self.bus.read_u16(RESET_VECTOR);
+    }
 
-        self.program_counter = self.memory.read_u16(0xFFFC);
+    /// Writes battery-backed cartridge RAM out, on shutdown or when the player saves.
+    pub fn flush_save(&self) -> Result<(), CartridgeError> {
+        self.bus.flush_save()
     }
 
-    pub fn main(&mut self, program: Vec<u8>) {
-        self.memory.load(program);
+    /// Copies a raw program to $8000. The reset vector is left to the bus, since on
+    /// a cartridge it lives in ROM.
+    pub fn load(&mut self, program: Vec<u8>) {
+        for (offset, byte) in program.iter().enumerate() {
+            self.bus
+                .write(PROGRAM_START.wrapping_add(offset as u16), *byte);
+        }
+    }
+
+    /// Loads and runs a raw program from $8000, whatever the reset vector says.
+    pub fn main(&mut self, program: Vec<u8>) -> Result<(), CpuError> {
+        self.load(program);
         self.reset();
-        self.run();
+        self.program_counter = PROGRAM_START;
+        self.run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Raw programs come without a cartridge, so point the reset vector at them
+    fn load_program(cpu: &mut CPU, program: Vec<u8>) {
+        cpu.load(program);
+        cpu.bus.write_u16(RESET_VECTOR, PROGRAM_START);
+    }
+
+    #[test]
+    fn test_load_leaves_reset_vector_alone() {
+        let mut cpu = CPU::new();
+        cpu.bus.write_u16(RESET_VECTOR, 0x1234);
+
+        cpu.load(vec![0xe8]);
+
+        assert_eq!(cpu.bus.read_u16(RESET_VECTOR), 0x1234);
+        assert_eq!(cpu.bus.read(PROGRAM_START), 0xe8);
+    }
+
+    #[test]
+    fn test_step_executes_single_instruction() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xa9, 0x42, 0x00]);
+        cpu.reset();
+
+        let step = cpu.step().unwrap();
+
+        assert_eq!(step.operation_code, 0xa9);
+        assert_eq!(step.addressing_mode, AddressingMode::Immediate);
+        assert_eq!(step.cycles, 2);
+        assert_eq!(step.program_counter, 0x8002);
+        assert_eq!(cpu.register_a, 0x42);
+        assert_eq!(cpu.register_x, 0x00);
+    }
+
+    #[test]
+    fn test_step_wraps_program_counter_at_end_of_memory() {
+        let mut cpu = CPU::new();
+        cpu.bus.write(0xFFFF, 0xe8);
+        cpu.program_counter = 0xFFFF;
+        cpu.step().unwrap();
+        assert_eq!(cpu.program_counter, 0x0000);
+
+        // LDA $1234 with its operand split across the wrap
+        cpu.bus.write(0xFFFE, 0xad);
+        cpu.bus.write(0xFFFF, 0x34);
+        cpu.bus.write(0x0000, 0x12);
+        cpu.bus.write(0x1234, 0x42);
+        cpu.program_counter = 0xFFFE;
+        cpu.step().unwrap();
+        assert_eq!(cpu.register_a, 0x42);
+        assert_eq!(cpu.program_counter, 0x0001);
+    }
+
+    #[test]
+    fn test_branches_cross_the_sign_boundary() {
+        let mut cpu = CPU::new();
+        // BNE +2 at $7FFD, then BNE -4 at $8001
+        cpu.bus.write(0x7FFD, 0xd0);
+        cpu.bus.write(0x7FFE, 0x02);
+        cpu.bus.write(0x8001, 0xd0);
+        cpu.bus.write(0x8002, 0xfc);
+        cpu.program_counter = 0x7FFD;
+
+        cpu.step().unwrap();
+        assert_eq!(cpu.program_counter, 0x8001);
+        cpu.step().unwrap();
+        assert_eq!(cpu.program_counter, 0x7FFF);
+    }
+
+    #[test]
+    fn test_step_follows_jumps() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0x4c, 0x34, 0x12]);
+        cpu.reset();
+
+        let step = cpu.step().unwrap();
+
+        assert_eq!(step.program_counter, 0x1234);
+        assert_eq!(cpu.program_counter, 0x1234);
+    }
+
+    #[test]
+    fn test_run_stops_at_break() {
+        let mut cpu = CPU::new();
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+        cpu.main(vec![0xa2, 0x05, 0xe8, 0x00, 0xe8]).unwrap();
+
+        assert_eq!(cpu.register_x, 0x06);
+        assert_eq!(cpu.program_counter, 0x9000);
+    }
+
+    #[test]
+    fn test_run_until_custom_condition() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8, 0xe8, 0xe8, 0xe8]);
+        cpu.reset();
+
+        cpu.run_until(|cpu, _| cpu.register_x == 3).unwrap();
+
+        assert_eq!(cpu.register_x, 3);
+        assert_eq!(cpu.program_counter, 0x8003);
+    }
+
+    #[test]
+    fn test_run_until_trap_on_branch_and_jump_to_self() {
+        let mut cpu = CPU::new();
+        // LDX #$03; DEX; BNE -3; BEQ -2
+        load_program(&mut cpu, vec![0xa2, 0x03, 0xca, 0xd0, 0xfd, 0xf0, 0xfe]);
+        cpu.reset();
+
+        assert_eq!(cpu.run_until_trap(), Ok(0x8005));
+        assert_eq!(cpu.register_x, 0);
+
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8, 0x4c, 0x01, 0x80]);
+        cpu.reset();
+
+        assert_eq!(cpu.run_until_trap(), Ok(0x8001));
+        assert_eq!(cpu.register_x, 1);
+    }
+
+    #[test]
+    fn test_step_accumulates_cycles() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xa9, 0x01, 0xe6, 0x10, 0xea]);
+        cpu.reset();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert_eq!(cpu.cycles, RESET_CYCLES + 2 + 5);
+    }
+
+    #[test]
+    fn test_step_page_cross_penalty_on_read() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xbd, 0xff, 0x20, 0xbd, 0x00, 0x20]);
+        cpu.reset();
+        cpu.register_x = 0x01;
+
+        assert_eq!(cpu.step().unwrap().cycles, 5);
+        assert_eq!(cpu.step().unwrap().cycles, 4);
+    }
+
+    #[test]
+    fn test_step_no_page_cross_penalty_on_store() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0x9d, 0xff, 0x20]);
+        cpu.reset();
+        cpu.register_x = 0x01;
+
+        assert_eq!(cpu.step().unwrap().cycles, 5);
+    }
+
+    #[test]
+    fn test_step_branch_penalties() {
+        let mut cpu = CPU::new();
+        // BNE not taken, BEQ taken on the same page, BEQ taken across a page
+        load_program(&mut cpu, vec![0xd0, 0x00, 0xf0, 0x00]);
+        cpu.reset();
+        cpu.status = 0b0000_0010;
+
+        assert_eq!(cpu.step().unwrap().cycles, 2);
+        assert_eq!(cpu.step().unwrap().cycles, 3);
+
+        cpu.program_counter = 0x80fd;
+        cpu.bus.write(0x80fd, 0xf0);
+        cpu.bus.write(0x80fe, 0x10);
+        assert_eq!(cpu.step().unwrap().cycles, 4);
+        assert_eq!(cpu.program_counter, 0x810f);
+    }
+
+    #[test]
+    fn test_step_unknown_operation_code() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xa9, 0x01, 0x8b]);
+        cpu.reset();
+        cpu.step().unwrap();
+
+        assert_eq!(
+            cpu.step(),
+            Err(CpuError::UnknownOperationCode {
+                program_counter: 0x8002,
+                operation_code: 0x8b,
+            })
+        );
+        assert_eq!(cpu.program_counter, 0x8002);
+    }
+
+    #[test]
+    fn test_run_propagates_errors() {
+        let mut cpu = CPU::new();
+
+        let result = cpu.main(vec![0xe8, 0x8b]);
+
+        assert!(matches!(
+            result,
+            Err(CpuError::UnknownOperationCode {
+                operation_code: 0x8b,
+                ..
+            })
+        ));
+        assert_eq!(cpu.register_x, 1);
+    }
+
+    #[test]
+    fn test_step_halted_cpu() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8]);
+        cpu.reset();
+        cpu.halted = true;
+
+        assert_eq!(cpu.step(), Err(CpuError::Halted));
+        assert_eq!(cpu.register_x, 0);
+
+        cpu.reset();
+        assert!(cpu.step().is_ok());
+    }
+
+    #[test]
+    fn test_step_services_nmi() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8]);
+        cpu.bus.write_u16(NMI_VECTOR, 0x9000);
+        cpu.reset();
+        cpu.status = 0b0000_0100;
+
+        cpu.trigger_nmi();
+        let step = cpu.step().unwrap();
+
+        assert_eq!(step.interrupt, Some(Interrupt::Nmi));
+        assert_eq!(step.cycles, INTERRUPT_CYCLES);
+        assert_eq!(cpu.program_counter, 0x9000);
+        // Reset left the stack pointer at $FA
+        assert_eq!(cpu.bus.read(0x01FA), 0x80);
+        assert_eq!(cpu.bus.read(0x01F9), 0x00);
+        assert_eq!(cpu.bus.read(0x01F8), 0b0010_0100);
+        assert!(!cpu.nmi_pending);
+    }
+
+    #[test]
+    fn test_step_irq_honours_interrupt_flag() {
+        let mut cpu = CPU::new();
+        // SEI, CLI, INX
+        load_program(&mut cpu, vec![0x78, 0x58, 0xe8]);
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+        cpu.reset();
+
+        cpu.step().unwrap();
+        cpu.set_irq(true);
+        let masked = cpu.step().unwrap();
+        assert_eq!(masked.interrupt, None);
+
+        let serviced = cpu.step().unwrap();
+        assert_eq!(serviced.interrupt, Some(Interrupt::Irq));
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(cpu.bus.read_u16(0x01F9), 0x8002);
+        assert_eq!(cpu.bus.read(0x01F8) & (1 << StatusBit::Break as u8), 0);
+    }
+
+    #[test]
+    fn test_reset_masks_irq_and_drops_stack_pointer() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8, 0x58, 0xe8]);
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+        cpu.set_irq(true);
+
+        cpu.reset();
+        assert_eq!(cpu.status, 0x24);
+        assert_eq!(cpu.stack_pointer, STACK_RESET - 3);
+
+        // INX runs with the line held; the IRQ waits for CLI
+        assert_eq!(cpu.step().unwrap().interrupt, None);
+        assert_eq!(cpu.register_x, 1);
+        assert_eq!(cpu.step().unwrap().operation_code, 0x58);
+        assert_eq!(cpu.step().unwrap().interrupt, Some(Interrupt::Irq));
+    }
+
+    #[test]
+    fn test_step_break_jumps_through_irq_vector() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0x00, 0xff]);
+        cpu.bus.write_u16(IRQ_VECTOR, 0x9000);
+        cpu.bus.write(0x9000, 0x40);
+        cpu.reset();
+
+        let step = cpu.step().unwrap();
+        assert_eq!(step.cycles, 7);
+        assert_eq!(step.interrupt, None);
+        assert_eq!(cpu.program_counter, 0x9000);
+
+        cpu.step().unwrap();
+        assert_eq!(cpu.program_counter, 0x8002);
+    }
+
+    #[test]
+    fn test_step_jam_halts_cpu() {
+        let mut cpu = CPU::new();
+        load_program(&mut cpu, vec![0xe8, 0x02, 0xe8]);
+        cpu.reset();
+
+        cpu.step().unwrap();
+        cpu.step().unwrap();
+
+        assert!(cpu.halted);
+        assert_eq!(cpu.program_counter, 0x8001);
+        assert_eq!(cpu.step(), Err(CpuError::Halted));
+    }
+
+    #[test]
+    fn test_step_unofficial_read_modify_write() {
+        let mut cpu = CPU::new();
+        // LDA #$05, DCP $10 with $10 holding $06
+        load_program(&mut cpu, vec![0xa9, 0x05, 0xc7, 0x10]);
+        cpu.bus.write(0x10, 0x06);
+        cpu.reset();
+
+        cpu.step().unwrap();
+        let step = cpu.step().unwrap();
+
+        assert_eq!(step.cycles, 5);
+        assert_eq!(cpu.bus.read(0x10), 0x05);
+        assert_eq!(
+            cpu.status & (1 << StatusBit::Zero as u8),
+            1 << StatusBit::Zero as u8
+        );
     }
 }
@@ -1,8 +1,12 @@
 use crate::cpu::addressing_mode::AddressingMode;
-use crate::cpu::memory::Memory;
-pub type ExecuteFunction = fn(&mut CPU, &AddressingMode);
+use crate::cpu::bus::Bus;
+use crate::cpu::cpu_error::CpuError;
+pub type ExecuteFunction = fn(&mut CPU, &AddressingMode) -> Result<(), CpuError>;
 pub const STACK: u16 = 0x0100;
 pub const STACK_RESET: u8 = 0xfd;
+pub const NMI_VECTOR: u16 = 0xFFFA;
+pub const RESET_VECTOR: u16 = 0xFFFC;
+pub const IRQ_VECTOR: u16 = 0xFFFE;
 pub struct CPU {
     pub register_a: u8,
     pub register_x: u8,
@@ -10,5 +14,10 @@ pub struct CPU {
     pub status: u8,
     pub program_counter: u16,
     pub stack_pointer: u8,
-    pub memory: Memory,
+    pub bus: Box<dyn Bus>,
+    pub cycles: u64,
+    pub extra_cycles: u8,
+    pub halted: bool,
+    pub nmi_pending: bool,
+    pub irq_line: bool,
 }
@@ -0,0 +1,193 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::bus::Bus;
+use crate::cpu::operation_codes::OPERATION_TABLE;
+use std::fmt;
+
+/// A single decoded instruction, or a `.byte` for data that does not decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassembledInstruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub mnemonic: &'static str,
+    pub operand: String,
+}
+
+impl fmt::Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.operand.is_empty() {
+            write!(f, "{}", self.mnemonic)
+        } else {
+            write!(f, "{} {}", self.mnemonic, self.operand)
+        }
+    }
+}
+
+pub fn format_operand(mode: &AddressingMode, address: u16, operand: &[u8]) -> String {
+    let byte = || operand[0];
+    let word = || ((operand[1] as u16) << 8) | (operand[0] as u16);
+    match mode {
+        AddressingMode::Implied | AddressingMode::NoneAddressing => String::new(),
+        AddressingMode::Accumulator => String::from("A"),
+        AddressingMode::Immediate => format!("#${:02X}", byte()),
+        AddressingMode::ZeroPage => format!("${:02X}", byte()),
+        AddressingMode::ZeroPage_X => format!("${:02X},X", byte()),
+        AddressingMode::ZeroPage_Y => format!("${:02X},Y", byte()),
+        AddressingMode::Absolute => format!("${:04X}", word()),
+        AddressingMode::Absolute_X => format!("${:04X},X", word()),
+        AddressingMode::Absolute_Y => format!("${:04X},Y", word()),
+        AddressingMode::Indirect => format!("(${:04X})", word()),
+        AddressingMode::Indirect_X => format!("(${:02X},X)", byte()),
+        AddressingMode::Indirect_Y => format!("(${:02X}),Y", byte()),
+        AddressingMode::Relative => {
+            let target = address.wrapping_add(2).wrapping_add(byte() as i8 as u16);
+            format!("${:04X}", target)
+        }
+    }
+}
+
+fn decode<F>(address: u16, code: u8, fetch: F) -> DisassembledInstruction
+where
+    F: Fn(u16) -> Option<u8>,
+{
+    let entry = OPERATION_TABLE[code as usize];
+    let operand: Option<Vec<u8>> = entry.and_then(|entry| {
+        (1..entry.operation.len as u16)
+            .map(|offset| fetch(address.wrapping_add(offset)))
+            .collect()
+    });
+
+    match (entry, operand) {
+        (Some(entry), Some(operand)) => {
+            let mut bytes = vec![code];
+            bytes.extend_from_slice(&operand);
+            DisassembledInstruction {
+                address,
+                bytes,
+                mnemonic: entry.operation_name.mnemonic(),
+                operand: format_operand(&entry.operation.addressing_mode, address, &operand),
+            }
+        }
+        // Unknown opcodes and instructions cut short by the end of the input
+        _ => DisassembledInstruction {
+            address,
+            bytes: vec![code],
+            mnemonic: ".byte",
+            operand: format!("${:02X}", code),
+        },
+    }
+}
+
+/// Decodes the whole slice, assuming its first byte lives at `start_address`.
+pub fn disassemble(bytes: &[u8], start_address: u16) -> Vec<DisassembledInstruction> {
+    let mut instructions = Vec::new();
+    let mut offset: usize = 0;
+    while offset < bytes.len() {
+        let address = start_address.wrapping_add(offset as u16);
+        let instruction = decode(address, bytes[offset], |at| {
+            bytes.get(at.wrapping_sub(start_address) as usize).copied()
+        });
+        offset += instruction.bytes.len();
+        instructions.push(instruction);
+    }
+    instructions
+}
+
+/// Decodes up to `count` instructions from the bus starting at `start_address`,
+/// stopping early at addresses the bus cannot peek.
+pub fn disassemble_memory(
+    bus: &dyn Bus,
+    start_address: u16,
+    count: usize,
+) -> Vec<DisassembledInstruction> {
+    let mut instructions = Vec::with_capacity(count);
+    let mut address = start_address;
+    for _ in 0..count {
+        let code = match bus.peek(address) {
+            Some(code) => code,
+            None => break,
+        };
+        let instruction = decode(address, code, |at| bus.peek(at));
+        address = address.wrapping_add(instruction.bytes.len() as u16);
+        instructions.push(instruction);
+    }
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::memory::Memory;
+
+    fn disassemble_text(bytes: &[u8], start_address: u16) -> Vec<String> {
+        disassemble(bytes, start_address)
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_disassemble_addressing_modes() {
+        let program = [
+            0xa9, 0xff, // LDA #$FF
+            0xb1, 0x20, // LDA ($20),Y
+            0xa1, 0x20, // LDA ($20,X)
+            0xb5, 0x10, // LDA $10,X
+            0xb6, 0x10, // LDX $10,Y
+            0xbd, 0x34, 0x12, // LDA $1234,X
+            0xb9, 0x34, 0x12, // LDA $1234,Y
+            0x6c, 0x00, 0x02, // JMP ($0200)
+            0x0a, // ASL A
+            0xaa, // TAX
+            0x00, // BRK
+        ];
+        assert_eq!(
+            disassemble_text(&program, 0x8000),
+            vec![
+                "LDA #$FF",
+                "LDA ($20),Y",
+                "LDA ($20,X)",
+                "LDA $10,X",
+                "LDX $10,Y",
+                "LDA $1234,X",
+                "LDA $1234,Y",
+                "JMP ($0200)",
+                "ASL A",
+                "TAX",
+                "BRK",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disassemble_relative_targets() {
+        assert_eq!(disassemble_text(&[0xd0, 0x10], 0xC000), vec!["BNE $C012"]);
+        assert_eq!(disassemble_text(&[0xd0, 0xfe], 0xC000), vec!["BNE $C000"]);
+    }
+
+    #[test]
+    fn test_disassemble_uses_operation_length() {
+        let instructions = disassemble(&[0x20, 0x00, 0x90, 0xe8], 0x8000);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].bytes, vec![0x20, 0x00, 0x90]);
+        assert_eq!(instructions[1].address, 0x8003);
+    }
+
+    #[test]
+    fn test_disassemble_unknown_and_truncated_bytes() {
+        assert_eq!(
+            disassemble_text(&[0x8b, 0xad, 0x00], 0x8000),
+            vec![".byte $8B", ".byte $AD", "BRK"]
+        );
+    }
+
+    #[test]
+    fn test_disassemble_memory() {
+        let mut memory = Memory::new();
+        memory.memory[0x0600..0x0605].copy_from_slice(&[0xa2, 0x08, 0xca, 0xd0, 0xfd]);
+
+        let instructions = disassemble_memory(&memory, 0x0600, 3);
+
+        let text: Vec<String> = instructions.iter().map(|i| i.to_string()).collect();
+        assert_eq!(text, vec!["LDX #$08", "DEX", "BNE $0602"]);
+    }
+}
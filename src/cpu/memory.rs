@@ -1,5 +1,9 @@
+use crate::cpu::bus::Bus;
+
+pub const MEMORY_SIZE: usize = 0x10000;
+
 pub struct Memory {
-    pub memory: [u8; 0xFFFF],
+    pub memory: [u8; MEMORY_SIZE],
 }
 
 impl Default for Memory {
@@ -11,28 +15,22 @@ impl Default for Memory {
 impl Memory {
     pub fn new() -> Self {
         Memory {
-            memory: [0; 0xFFFF],
+            memory: [0; MEMORY_SIZE],
         }
     }
+}
 
-    pub fn read_u16(&mut self, address: u16) -> u16 {
-        // TODO: mutable?
-        let left: u16 = self.memory[address as usize] as u16;
-        let right: u16 = self.memory[address as usize + 1] as u16;
-        (right << 8) | left
+impl Bus for Memory {
+    fn read(&mut self, address: u16) -> u8 {
+        self.memory[address as usize]
     }
 
-    pub fn write_u16(&mut self, address: u16, data: u16) {
-        let right: u8 = (data >> 8) as u8;
-        let left: u8 = (data & 0xff) as u8; // TODO: not needed?
-        self.memory[address as usize] = left;
-        self.memory[address as usize + 1] = right;
+    fn write(&mut self, address: u16, value: u8) {
+        self.memory[address as usize] = value;
     }
 
-    pub fn load(&mut self, program: Vec<u8>) {
-        let start_position: usize = 0x8000;
-        self.memory[start_position..(start_position + program.len())].copy_from_slice(&program[..]);
-        self.write_u16(0xFFFC, 0x8000);
+    fn peek(&self, address: u16) -> Option<u8> {
+        Some(self.memory[address as usize])
     }
 }
 
@@ -66,4 +64,24 @@ mod tests {
 
         assert_eq!(value, memory.read_u16(address));
     }
+
+    #[test]
+    fn test_read_write_u16_last_address() {
+        let mut memory: Memory = Memory::new();
+
+        memory.write_u16(0xFFFE, 0xABCD);
+
+        assert_eq!(memory.read_u16(0xFFFE), 0xABCD);
+        assert_eq!(memory.memory[0xFFFF], 0xAB);
+    }
+
+    #[test]
+    fn test_peek_matches_read() {
+        let mut memory: Memory = Memory::new();
+
+        memory.write(0x1234, 0x56);
+
+        assert_eq!(memory.peek(0x1234), Some(0x56));
+        assert_eq!(memory.read(0x1234), 0x56);
+    }
 }
@@ -0,0 +1,250 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::cartridge_model::Cartridge;
+use crate::cartridge::mapper::{create_mapper, Mapper};
+use crate::cartridge::save_file::SaveFile;
+use crate::cpu::bus::Bus;
+use crate::ppu::ppu_model::PPU;
+
+pub const RAM_SIZE: usize = 0x0800;
+const RAM_END: u16 = 0x1FFF;
+const RAM_MIRROR_MASK: u16 = 0x07FF;
+
+const PPU_REGISTERS_START: u16 = 0x2000;
+const PPU_REGISTERS_END: u16 = 0x3FFF;
+
+const IO_REGISTERS_START: u16 = 0x4000;
+const IO_REGISTERS_END: u16 = 0x4017;
+const IO_REGISTER_COUNT: usize = 0x18;
+
+pub const CARTRIDGE_START: u16 = 0x4020;
+
+/// The NES CPU address space.
+///
+/// | Range         | Target                                   |
+/// |---------------|------------------------------------------|
+/// | $0000-$1FFF   | 2 KiB internal RAM, mirrored every $0800 |
+/// | $2000-$3FFF   | PPU registers, mirrored every 8 bytes    |
+/// | $4000-$4017   | APU and I/O registers                    |
+/// | $4018-$401F   | CPU test mode, unused                    |
+/// | $4020-$FFFF   | Cartridge                                |
+///
+/// A battery-backed cartridge's `save_file` is flushed when the map is dropped, or
+/// earlier through `flush_save`.
+pub struct MemoryMap {
+    pub ram: [u8; RAM_SIZE],
+    pub ppu: PPU,
+    pub io_registers: [u8; IO_REGISTER_COUNT],
+    pub cartridge: Box<dyn Mapper>,
+    pub save_file: Option<SaveFile>,
+}
+
+impl MemoryMap {
+    pub fn new(cartridge: Box<dyn Mapper>) -> Self {
+        MemoryMap {
+            ram: [0; RAM_SIZE],
+            ppu: PPU::new(),
+            io_registers: [0; IO_REGISTER_COUNT],
+            cartridge,
+            save_file: None,
+        }
+    }
+
+    /// Builds the cartridge's mapper and keeps its save file, if it has one.
+    pub fn from_cartridge(mut cartridge: Cartridge) -> Result<Self, CartridgeError> {
+        let save_file = cartridge.save_file.take();
+        let mut memory_map = MemoryMap::new(create_mapper(cartridge)?);
+        memory_map.save_file = save_file;
+        Ok(memory_map)
+    }
+}
+
+impl Drop for MemoryMap {
+    // Drop cannot report a failure; hosts that need to know call `flush_save` first
+    fn drop(&mut self) {
+        let _ = self.flush_save();
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: u16) -> u8 {
+        match address {
+            0x0000..=RAM_END => self.ram[(address & RAM_MIRROR_MASK) as usize],
+            PPU_REGISTERS_START..=PPU_REGISTERS_END => {
+                self.ppu.read_register(address, self.cartridge.as_mut())
+            }
+            IO_REGISTERS_START..=IO_REGISTERS_END => {
+                self.io_registers[(address - IO_REGISTERS_START) as usize]
+            }
+            CARTRIDGE_START..=0xFFFF => self.cartridge.cpu_read(address),
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=RAM_END => self.ram[(address & RAM_MIRROR_MASK) as usize] = value,
+            PPU_REGISTERS_START..=PPU_REGISTERS_END => {
+                self.ppu
+                    .write_register(address, value, self.cartridge.as_mut())
+            }
+            IO_REGISTERS_START..=IO_REGISTERS_END => {
+                self.io_registers[(address - IO_REGISTERS_START) as usize] = value
+            }
+            CARTRIDGE_START..=0xFFFF => self.cartridge.cpu_write(address, value),
+            _ => {}
+        }
+    }
+
+    fn dummy_write(&mut self, address: u16, value: u8) {
+        match address {
+            CARTRIDGE_START..=0xFFFF => self.cartridge.cpu_dummy_write(address, value),
+            _ => self.write(address, value),
+        }
+    }
+
+    // Register reads can have side effects (e.g. PPUSTATUS clears vblank), so only
+    // RAM and the cartridge are peekable
+    fn peek(&self, address: u16) -> Option<u8> {
+        match address {
+            0x0000..=RAM_END => Some(self.ram[(address & RAM_MIRROR_MASK) as usize]),
+            CARTRIDGE_START..=0xFFFF => self.cartridge.cpu_peek(address),
+            _ => None,
+        }
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        self.cartridge.cpu_clock(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        self.cartridge.irq()
+    }
+
+    fn flush_save(&self) -> Result<(), CartridgeError> {
+        match &self.save_file {
+            Some(save_file) => save_file.flush(self.cartridge.prg_ram()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::header::{CHR_ROM_BANK_SIZE, PRG_ROM_BANK_SIZE};
+    use crate::cartridge::nrom::Nrom;
+    use crate::cartridge::test_rom::{cartridge, temp_path};
+    use crate::cpu::cpu_model::CPU;
+    use std::fs;
+
+    // NROM-256 with `program` at $8000 and every vector pointing at it
+    fn create_test_cartridge(program: &[u8], flags_6: u8) -> Cartridge {
+        let mut prg_rom = vec![0; 2 * PRG_ROM_BANK_SIZE];
+        prg_rom[..program.len()].copy_from_slice(program);
+        prg_rom[0x7FFA..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80, 0x00, 0x90]);
+        let chr_rom = vec![0; CHR_ROM_BANK_SIZE];
+        cartridge(0, 0, prg_rom, chr_rom, flags_6)
+    }
+
+    fn create_test_memory_map(program: &[u8]) -> MemoryMap {
+        MemoryMap::new(Box::new(Nrom::new(create_test_cartridge(program, 0))))
+    }
+
+    // LDA #$99; STA $6010; BRK on a battery-backed board saving to `name`
+    fn create_saving_cpu(name: &str) -> (CPU, SaveFile) {
+        let battery = 0b0000_0010;
+        let mut cartridge = create_test_cartridge(&[0xa9, 0x99, 0x8d, 0x10, 0x60, 0x00], battery);
+        let save_file = SaveFile::for_rom(temp_path(name));
+        cartridge.save_file = Some(save_file.clone());
+        let mut cpu = CPU::with_bus(Box::new(MemoryMap::from_cartridge(cartridge).unwrap()));
+        cpu.reset();
+        cpu.run().unwrap();
+        (cpu, save_file)
+    }
+
+    #[test]
+    fn test_ram_is_mirrored_every_2_kib() {
+        let mut memory_map = create_test_memory_map(&[]);
+
+        memory_map.write(0x0012, 0xAB);
+
+        assert_eq!(memory_map.read(0x0812), 0xAB);
+        assert_eq!(memory_map.read(0x1012), 0xAB);
+        assert_eq!(memory_map.read(0x1812), 0xAB);
+
+        memory_map.write(0x1FFF, 0xCD);
+        assert_eq!(memory_map.ram[0x07FF], 0xCD);
+    }
+
+    #[test]
+    fn test_ppu_registers_are_mirrored_every_8_bytes() {
+        let mut memory_map = create_test_memory_map(&[]);
+
+        // PPUADDR through two different mirrors, then PPUDATA
+        memory_map.write(0x3456, 0x21);
+        memory_map.write(0x3FFE, 0x08);
+        memory_map.write(0x200F, 0x42);
+
+        assert_eq!(memory_map.ppu.vram[0x108], 0x42);
+        assert_eq!(memory_map.ppu.vram_address, 0x2109);
+        assert_eq!(memory_map.peek(0x2007), None);
+    }
+
+    #[test]
+    fn test_io_registers_and_test_mode_range() {
+        let mut memory_map = create_test_memory_map(&[]);
+
+        memory_map.write(0x4016, 0x01);
+        memory_map.write(0x401A, 0xFF);
+
+        assert_eq!(memory_map.io_registers[0x16], 0x01);
+        assert_eq!(memory_map.read(0x401A), 0x00);
+    }
+
+    #[test]
+    fn test_cartridge_space_is_routed_to_the_mapper() {
+        let mut memory_map = create_test_memory_map(&[0xEA]);
+
+        memory_map.write(0x6000, 0x11);
+
+        assert_eq!(memory_map.read(0x6000), 0x11);
+        assert_eq!(memory_map.read(0x8000), 0xEA);
+        assert_eq!(memory_map.read_u16(0xFFFE), 0x9000);
+        assert_eq!(memory_map.peek(0xFFFF), Some(0x90));
+    }
+
+    #[test]
+    fn test_cpu_runs_on_memory_map() {
+        // LDA #$07; STA $0810; LDX $10; BRK
+        let program = [0xa9, 0x07, 0x8d, 0x10, 0x08, 0xa6, 0x10, 0x00];
+        let mut cpu = CPU::with_bus(Box::new(create_test_memory_map(&program)));
+        cpu.reset();
+
+        cpu.run().unwrap();
+
+        assert_eq!(cpu.register_x, 0x07);
+    }
+
+    #[test]
+    fn test_cpu_flushes_battery_save() {
+        let (cpu, save_file) = create_saving_cpu("cpu_flush.nes");
+
+        cpu.flush_save().unwrap();
+        let save = fs::read(save_file.path()).unwrap();
+        drop(cpu);
+        fs::remove_file(save_file.path()).unwrap();
+
+        assert_eq!(save[0x10], 0x99);
+    }
+
+    #[test]
+    fn test_dropping_memory_map_flushes_battery_save() {
+        let (cpu, save_file) = create_saving_cpu("drop_flush.nes");
+
+        drop(cpu);
+        let save = fs::read(save_file.path()).unwrap();
+        fs::remove_file(save_file.path()).unwrap();
+
+        assert_eq!(save[0x10], 0x99);
+    }
+}
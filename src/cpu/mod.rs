@@ -1,8 +1,16 @@
 pub mod addressing_mode;
+pub mod assembler;
 pub mod bitwise_operation;
+pub mod bus;
+pub mod cpu_error;
 pub mod cpu_functions;
 pub mod cpu_instructions;
 pub mod cpu_model;
+pub mod disassembler;
+pub mod interrupt;
 pub mod memory;
+pub mod memory_map;
 pub mod operation_codes;
 pub mod status_bit;
+pub mod step_result;
+pub mod trace;
@@ -1,9 +1,8 @@
 use crate::cpu::addressing_mode::AddressingMode;
 use crate::cpu::cpu_functions;
 use crate::cpu::cpu_model::ExecuteFunction;
-use lazy_static::lazy_static;
-use std::collections::HashMap;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperationName {
     AddWithCarry,
     ArithmeticShiftLeft,
@@ -23,22 +22,27 @@ pub enum OperationName {
     Compare,
     CompareX,
     CompareY,
+    DecrementAndCompare,
     DecrementMemory,
     DecrementXRegister,
     DecrementYRegister,
     ExclusiveOR,
     ForceInterrupt,
+    IncrementAndSubstract,
     IncrementMemory,
     IncrementXRegister,
     IncrementYRegister,
+    Jam,
     Jump,
     JumpToSubroutine,
     LoadAccumulator,
+    LoadAccumulatorAndX,
     LoadXRegister,
     LoadYRegister,
     LogicalAND,
     LogicalInclusiveOR,
     LogicalShiftRight,
+    NoOperation,
     PullAccumulator,
     PullProcessorStatus,
     PushAccumulator,
@@ -46,11 +50,16 @@ pub enum OperationName {
     ReturnFromInterrupt,
     ReturnFromSubroutine,
     RotateLeft,
+    RotateLeftAndAND,
     RotateRight,
+    RotateRightAndAdd,
     SetCarryFlag,
     SetDecimalFlag,
     SetInterruptDisable,
+    ShiftLeftAndOR,
+    ShiftRightAndExclusiveOR,
     StoreAccumulator,
+    StoreAccumulatorAndX,
     StoreXRegister,
     StoreYRegister,
     SubstractWithCarry,
@@ -60,6 +69,99 @@ pub enum OperationName {
     TransferXToAccumulator,
     TransferXToStackPointer,
     TransferYToAccumulator,
+    UnofficialNoOperation,
+    UnofficialSubstractWithCarry,
+}
+
+impl OperationName {
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            OperationName::AddWithCarry => "ADC",
+            OperationName::ArithmeticShiftLeft => "ASL",
+            OperationName::BitTest => "BIT",
+            OperationName::BranchIfCarryClear => "BCC",
+            OperationName::BranchIfCarrySet => "BCS",
+            OperationName::BranchIfEqual => "BEQ",
+            OperationName::BranchIfMinus => "BMI",
+            OperationName::BranchIfNotEqual => "BNE",
+            OperationName::BranchIfOverflowClear => "BVC",
+            OperationName::BranchIfOverflowSet => "BVS",
+            OperationName::BranchIfPositive => "BPL",
+            OperationName::ClearCarryFlag => "CLC",
+            OperationName::ClearDecimalMode => "CLD",
+            OperationName::ClearInterruptDisable => "CLI",
+            OperationName::ClearOverflowFlag => "CLV",
+            OperationName::Compare => "CMP",
+            OperationName::CompareX => "CPX",
+            OperationName::CompareY => "CPY",
+            OperationName::DecrementAndCompare => "DCP",
+            OperationName::DecrementMemory => "DEC",
+            OperationName::DecrementXRegister => "DEX",
+            OperationName::DecrementYRegister => "DEY",
+            OperationName::ExclusiveOR => "EOR",
+            OperationName::ForceInterrupt => "BRK",
+            OperationName::IncrementAndSubstract => "ISB",
+            OperationName::IncrementMemory => "INC",
+            OperationName::IncrementXRegister => "INX",
+            OperationName::IncrementYRegister => "INY",
+            OperationName::Jam => "JAM",
+            OperationName::Jump => "JMP",
+            OperationName::JumpToSubroutine => "JSR",
+            OperationName::LoadAccumulator => "LDA",
+            OperationName::LoadAccumulatorAndX => "LAX",
+            OperationName::LoadXRegister => "LDX",
+            OperationName::LoadYRegister => "LDY",
+            OperationName::LogicalAND => "AND",
+            OperationName::LogicalInclusiveOR => "ORA",
+            OperationName::LogicalShiftRight => "LSR",
+            OperationName::NoOperation => "NOP",
+            OperationName::PullAccumulator => "PLA",
+            OperationName::PullProcessorStatus => "PLP",
+            OperationName::PushAccumulator => "PHA",
+            OperationName::PushProcessorStatus => "PHP",
+            OperationName::ReturnFromInterrupt => "RTI",
+            OperationName::ReturnFromSubroutine => "RTS",
+            OperationName::RotateLeft => "ROL",
+            OperationName::RotateLeftAndAND => "RLA",
+            OperationName::RotateRight => "ROR",
+            OperationName::RotateRightAndAdd => "RRA",
+            OperationName::SetCarryFlag => "SEC",
+            OperationName::SetDecimalFlag => "SED",
+            OperationName::SetInterruptDisable => "SEI",
+            OperationName::ShiftLeftAndOR => "SLO",
+            OperationName::ShiftRightAndExclusiveOR => "SRE",
+            OperationName::StoreAccumulator => "STA",
+            OperationName::StoreAccumulatorAndX => "SAX",
+            OperationName::StoreXRegister => "STX",
+            OperationName::StoreYRegister => "STY",
+            OperationName::SubstractWithCarry => "SBC",
+            OperationName::TransferAccumulatorToX => "TAX",
+            OperationName::TransferAccumulatorToY => "TAY",
+            OperationName::TransferStackPointerToX => "TSX",
+            OperationName::TransferXToAccumulator => "TXA",
+            OperationName::TransferXToStackPointer => "TXS",
+            OperationName::TransferYToAccumulator => "TYA",
+            OperationName::UnofficialNoOperation => "NOP",
+            OperationName::UnofficialSubstractWithCarry => "SBC",
+        }
+    }
+
+    pub fn is_unofficial(&self) -> bool {
+        matches!(
+            self,
+            OperationName::DecrementAndCompare
+                | OperationName::IncrementAndSubstract
+                | OperationName::Jam
+                | OperationName::LoadAccumulatorAndX
+                | OperationName::RotateLeftAndAND
+                | OperationName::RotateRightAndAdd
+                | OperationName::ShiftLeftAndOR
+                | OperationName::ShiftRightAndExclusiveOR
+                | OperationName::StoreAccumulatorAndX
+                | OperationName::UnofficialNoOperation
+                | OperationName::UnofficialSubstractWithCarry
+        )
+    }
 }
 
 pub struct Operation {
@@ -69,7 +171,7 @@ pub struct Operation {
     pub addressing_mode: AddressingMode,
 }
 impl Operation {
-    fn new(operation_code: u8, len: u8, cycles: u8, addressing_mode: AddressingMode) -> Self {
+    const fn new(operation_code: u8, len: u8, cycles: u8, addressing_mode: AddressingMode) -> Self {
         Operation {
             operation_code,
             len,
@@ -80,13 +182,13 @@ impl Operation {
 }
 pub struct OperationCodes {
     pub operation_name: OperationName,
-    pub operations: Vec<Operation>,
+    pub operations: &'static [Operation],
     pub execute_function: ExecuteFunction,
 }
 impl OperationCodes {
-    fn new(
+    const fn new(
         operation_name: OperationName,
-        operations: Vec<Operation>,
+        operations: &'static [Operation],
         execute_function: ExecuteFunction,
     ) -> Self {
         OperationCodes {
@@ -97,449 +199,762 @@ impl OperationCodes {
     }
 }
 
-lazy_static! {
-    pub static ref CPU_OPS_CODES: Vec<OperationCodes> = vec![
-        OperationCodes::new(
-            OperationName::BranchIfCarryClear,
-            vec![Operation::new(0x90, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_carry_clear
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfCarrySet,
-            vec![Operation::new(0xb0, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_carry_set
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfEqual,
-            vec![Operation::new(0xf0, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_equal
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfMinus,
-            vec![Operation::new(0x30, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_minus
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfNotEqual,
-            vec![Operation::new(0xd0, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_not_equal
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfPositive,
-            vec![Operation::new(0x10, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_positive
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfOverflowClear,
-            vec![Operation::new(0x50, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_overflow_clear
-        ),
-        OperationCodes::new(
-            OperationName::BranchIfOverflowSet,
-            vec![Operation::new(0x70, 2, 2, AddressingMode::Relative),],
-            cpu_functions::branch_if_overflow_set
-        ),
-        OperationCodes::new(
-            OperationName::ForceInterrupt,
-            vec![Operation::new(0x00, 1, 7, AddressingMode::NoneAddressing),],
-            cpu_functions::force_interruptions
-        ),
-        OperationCodes::new(
-            OperationName::TransferAccumulatorToX,
-            vec![Operation::new(0xaa, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::transfer_accumulator_to_x
-        ),
-        OperationCodes::new(
-            OperationName::TransferAccumulatorToY,
-            vec![Operation::new(0xaa, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::transfer_accumulator_to_y
-        ),
-        OperationCodes::new(
-            OperationName::IncrementXRegister,
-            vec![Operation::new(0xe8, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::increment_x_register
-        ),
-        OperationCodes::new(
-            OperationName::IncrementYRegister,
-            vec![Operation::new(0xc8, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::increment_y_register
-        ),
-        OperationCodes::new(
-            OperationName::IncrementMemory,
-            vec![
-                Operation::new(0xe6, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0xf6, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0xee, 3, 6, AddressingMode::Absolute),
-                Operation::new(0xfe, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::increment_memory
-        ),
-        OperationCodes::new(
-            OperationName::Jump,
-            vec![
-                Operation::new(0x4c, 3, 3, AddressingMode::Absolute),
-                Operation::new(0x6c, 3, 5, AddressingMode::Indirect),
-            ],
-            cpu_functions::jump
-        ),
-        OperationCodes::new(
-            OperationName::JumpToSubroutine,
-            vec![Operation::new(0x20, 3, 6, AddressingMode::Absolute),],
-            cpu_functions::jump_to_subroutine
-        ),
-        OperationCodes::new(
-            OperationName::DecrementXRegister,
-            vec![Operation::new(0xca, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::decrement_x_register
-        ),
-        OperationCodes::new(
-            OperationName::DecrementYRegister,
-            vec![Operation::new(0x88, 1, 2, AddressingMode::NoneAddressing)],
-            cpu_functions::decrement_y_register
-        ),
-        OperationCodes::new(
-            OperationName::DecrementMemory,
-            vec![
-                Operation::new(0xc6, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0xd6, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0xce, 3, 6, AddressingMode::Absolute),
-                Operation::new(0xde, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::decrement_memory
-        ),
-        OperationCodes::new(
-            OperationName::LoadAccumulator,
-            vec![
-                Operation::new(0xa9, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xa5, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xb5, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0xad, 3, 4, AddressingMode::Absolute),
-                Operation::new(0xbd, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0xb9, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0xa1, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0xb1, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::load_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::LoadXRegister,
-            vec![
-                Operation::new(0xA2, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xA6, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xB6, 2, 4, AddressingMode::ZeroPage_Y),
-                Operation::new(0xAE, 3, 4, AddressingMode::Absolute),
-                Operation::new(0xBE, 3, 4, AddressingMode::Absolute_Y),
-            ],
-            cpu_functions::load_x_register
-        ),
-        OperationCodes::new(
-            OperationName::LoadYRegister,
-            vec![
-                Operation::new(0xA0, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xA4, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xB4, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0xAC, 3, 4, AddressingMode::Absolute),
-                Operation::new(0xBC, 3, 4, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::load_y_register
-        ),
-        OperationCodes::new(
-            OperationName::ReturnFromInterrupt,
-            vec![Operation::new(0x40, 1, 6, AddressingMode::Implied),],
-            cpu_functions::return_from_interrupt
-        ),
-        OperationCodes::new(
-            OperationName::ReturnFromSubroutine,
-            vec![Operation::new(0x60, 1, 6, AddressingMode::Implied),],
-            cpu_functions::return_from_subroutine
-        ),
-        OperationCodes::new(
-            OperationName::StoreAccumulator,
-            vec![
-                Operation::new(0x85, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x95, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x8d, 3, 4, AddressingMode::Absolute),
-                Operation::new(0x9d, 3, 5, AddressingMode::Absolute_X),
-                Operation::new(0x99, 3, 5, AddressingMode::Absolute_Y),
-                Operation::new(0x81, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0x91, 2, 6, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::store_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::StoreXRegister,
-            vec![
-                Operation::new(0x86, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x96, 2, 4, AddressingMode::ZeroPage_Y),
-                Operation::new(0x8e, 3, 4, AddressingMode::Absolute),
-            ],
-            cpu_functions::store_x_register
-        ),
-        OperationCodes::new(
-            OperationName::StoreYRegister,
-            vec![
-                Operation::new(0x84, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x94, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x8c, 3, 4, AddressingMode::Absolute),
-            ],
-            cpu_functions::store_y_register
-        ),
-        OperationCodes::new(
-            OperationName::Compare,
-            vec![
-                Operation::new(0xC9, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xC5, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xD5, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0xCD, 3, 4, AddressingMode::Absolute),
-                Operation::new(0xDD, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0xD9, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0xC1, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0xD1, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::compare_a
-        ),
-        OperationCodes::new(
-            OperationName::CompareX,
-            vec![
-                Operation::new(0xE0, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xE4, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xEC, 3, 4, AddressingMode::Absolute)
-            ],
-            cpu_functions::compare_x
-        ),
-        OperationCodes::new(
-            OperationName::CompareY,
-            vec![
-                Operation::new(0xC0, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xC4, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xCC, 3, 4, AddressingMode::Absolute)
-            ],
-            cpu_functions::compare_y
-        ),
-        OperationCodes::new(
-            OperationName::AddWithCarry,
-            vec![
-                Operation::new(0x69, 2, 2, AddressingMode::Immediate),
-                Operation::new(0x65, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x75, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x6d, 3, 4, AddressingMode::Absolute),
-                Operation::new(0x7d, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0x79, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0x61, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0x71, 2, 5, AddressingMode::Indirect_Y)
-            ],
-            cpu_functions::add_with_carry
-        ),
-        OperationCodes::new(
-            OperationName::SubstractWithCarry,
-            vec![
-                Operation::new(0xe9, 2, 2, AddressingMode::Immediate),
-                Operation::new(0xe5, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0xf5, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0xed, 3, 4, AddressingMode::Absolute),
-                Operation::new(0xfd, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0xf9, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0xe1, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0xf1, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::substract_with_carry
-        ),
-        OperationCodes::new(
-            OperationName::TransferStackPointerToX,
-            vec![Operation::new(0xba, 1, 2, AddressingMode::NoneAddressing),],
-            cpu_functions::transfer_stack_pointer_to_x
-        ),
-        OperationCodes::new(
-            OperationName::TransferXToAccumulator,
-            vec![Operation::new(0x8a, 1, 2, AddressingMode::NoneAddressing),],
-            cpu_functions::transfer_x_to_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::TransferXToStackPointer,
-            vec![Operation::new(0x9a, 1, 2, AddressingMode::NoneAddressing),],
-            cpu_functions::transfer_x_to_stack_pointer
-        ),
-        OperationCodes::new(
-            OperationName::TransferYToAccumulator,
-            vec![Operation::new(0x98, 1, 2, AddressingMode::NoneAddressing),],
-            cpu_functions::transfer_y_to_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::ArithmeticShiftLeft,
-            vec![
-                Operation::new(0x06, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0x16, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0x0e, 3, 6, AddressingMode::Absolute),
-                Operation::new(0x1e, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::arithmetic_shift_left
-        ),
-        OperationCodes::new(
-            OperationName::ArithmeticShiftLeft,
-            vec![Operation::new(0x0a, 1, 2, AddressingMode::Accumulator)],
-            cpu_functions::arithmetic_shift_left_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::BitTest,
-            vec![
-                Operation::new(0x24, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x2c, 3, 4, AddressingMode::Absolute),
-            ],
-            cpu_functions::bit_test
-        ),
-        OperationCodes::new(
-            OperationName::ClearCarryFlag,
-            vec![Operation::new(0x18, 1, 2, AddressingMode::Implied),],
-            cpu_functions::clear_carry_flag
-        ),
-        OperationCodes::new(
-            OperationName::ClearDecimalMode,
-            vec![Operation::new(0xd8, 1, 2, AddressingMode::Implied),],
-            cpu_functions::clear_decimal_mode
-        ),
-        OperationCodes::new(
-            OperationName::ClearInterruptDisable,
-            vec![Operation::new(0x58, 1, 2, AddressingMode::Implied),],
-            cpu_functions::clear_interrupt_disable
-        ),
-        OperationCodes::new(
-            OperationName::ClearOverflowFlag,
-            vec![Operation::new(0xb8, 1, 2, AddressingMode::Implied),],
-            cpu_functions::clear_overflow_flag
-        ),
-        OperationCodes::new(
-            OperationName::ExclusiveOR,
-            vec![
-                Operation::new(0x49, 2, 2, AddressingMode::Immediate),
-                Operation::new(0x45, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x55, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x4d, 3, 4, AddressingMode::Absolute),
-                Operation::new(0x5d, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0x59, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0x41, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0x51, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::exclusive_or
-        ),
-        OperationCodes::new(
-            OperationName::LogicalAND,
-            vec![
-                Operation::new(0x29, 2, 2, AddressingMode::Immediate),
-                Operation::new(0x25, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x35, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x2d, 3, 4, AddressingMode::Absolute),
-                Operation::new(0x3d, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0x39, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0x21, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0x31, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::logical_and
-        ),
-        OperationCodes::new(
-            OperationName::LogicalInclusiveOR,
-            vec![
-                Operation::new(0x09, 2, 2, AddressingMode::Immediate),
-                Operation::new(0x05, 2, 3, AddressingMode::ZeroPage),
-                Operation::new(0x15, 2, 4, AddressingMode::ZeroPage_X),
-                Operation::new(0x0d, 3, 4, AddressingMode::Absolute),
-                Operation::new(0x1d, 3, 4, AddressingMode::Absolute_X),
-                Operation::new(0x19, 3, 4, AddressingMode::Absolute_Y),
-                Operation::new(0x01, 2, 6, AddressingMode::Indirect_X),
-                Operation::new(0x11, 2, 5, AddressingMode::Indirect_Y),
-            ],
-            cpu_functions::logical_inclusive_or
-        ),
-        OperationCodes::new(
-            OperationName::LogicalShiftRight,
-            vec![
-                Operation::new(0x46, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0x56, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0x4e, 3, 6, AddressingMode::Absolute),
-                Operation::new(0x5e, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::logical_shift_right
-        ),
-        OperationCodes::new(
-            OperationName::LogicalShiftRight,
-            vec![Operation::new(0x4a, 1, 2, AddressingMode::Accumulator)],
-            cpu_functions::logical_shift_right_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::PullAccumulator,
-            vec![Operation::new(0x68, 1, 4, AddressingMode::Implied),],
-            cpu_functions::pull_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::PullProcessorStatus,
-            vec![Operation::new(0x28, 1, 4, AddressingMode::Implied),],
-            cpu_functions::pull_processor_status
-        ),
-        OperationCodes::new(
-            OperationName::PushAccumulator,
-            vec![Operation::new(0x48, 1, 3, AddressingMode::Implied),],
-            cpu_functions::push_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::PushProcessorStatus,
-            vec![Operation::new(0x08, 1, 3, AddressingMode::Implied),],
-            cpu_functions::push_processor_status
-        ),
-        OperationCodes::new(
-            OperationName::RotateLeft,
-            vec![
-                Operation::new(0x26, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0x36, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0x2e, 3, 6, AddressingMode::Absolute),
-                Operation::new(0x3e, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::rotate_left
-        ),
-        OperationCodes::new(
-            OperationName::RotateLeft,
-            vec![Operation::new(0x2a, 1, 2, AddressingMode::Accumulator)],
-            cpu_functions::rotate_left_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::RotateRight,
-            vec![
-                Operation::new(0x66, 2, 5, AddressingMode::ZeroPage),
-                Operation::new(0x76, 2, 6, AddressingMode::ZeroPage_X),
-                Operation::new(0x6e, 3, 6, AddressingMode::Absolute),
-                Operation::new(0x7e, 3, 7, AddressingMode::Absolute_X),
-            ],
-            cpu_functions::rotate_right
-        ),
-        OperationCodes::new(
-            OperationName::RotateRight,
-            vec![Operation::new(0x6a, 1, 2, AddressingMode::Accumulator)],
-            cpu_functions::rotate_right_accumulator
-        ),
-        OperationCodes::new(
-            OperationName::SetCarryFlag,
-            vec![Operation::new(0x38, 1, 2, AddressingMode::Implied),],
-            cpu_functions::set_carry_flag
-        ),
-        OperationCodes::new(
-            OperationName::SetDecimalFlag,
-            vec![Operation::new(0xf8, 1, 2, AddressingMode::Implied),],
-            cpu_functions::set_decimal_flag
-        ),
-        OperationCodes::new(
-            OperationName::SetInterruptDisable,
-            vec![Operation::new(0x78, 1, 2, AddressingMode::Implied),],
-            cpu_functions::set_interrupt_disable
-        )
+pub static CPU_OPS_CODES: &[OperationCodes] = &[
+    OperationCodes::new(
+        OperationName::BranchIfCarryClear,
+        &[Operation::new(0x90, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_carry_clear,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfCarrySet,
+        &[Operation::new(0xb0, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_carry_set,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfEqual,
+        &[Operation::new(0xf0, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_equal,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfMinus,
+        &[Operation::new(0x30, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_minus,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfNotEqual,
+        &[Operation::new(0xd0, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_not_equal,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfPositive,
+        &[Operation::new(0x10, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_positive,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfOverflowClear,
+        &[Operation::new(0x50, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_overflow_clear,
+    ),
+    OperationCodes::new(
+        OperationName::BranchIfOverflowSet,
+        &[Operation::new(0x70, 2, 2, AddressingMode::Relative)],
+        cpu_functions::branch_if_overflow_set,
+    ),
+    OperationCodes::new(
+        OperationName::ForceInterrupt,
+        &[Operation::new(0x00, 1, 7, AddressingMode::NoneAddressing)],
+        cpu_functions::force_interruptions,
+    ),
+    OperationCodes::new(
+        OperationName::TransferAccumulatorToX,
+        &[Operation::new(0xaa, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_accumulator_to_x,
+    ),
+    OperationCodes::new(
+        OperationName::TransferAccumulatorToY,
+        &[Operation::new(0xa8, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_accumulator_to_y,
+    ),
+    OperationCodes::new(
+        OperationName::IncrementXRegister,
+        &[Operation::new(0xe8, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::increment_x_register,
+    ),
+    OperationCodes::new(
+        OperationName::IncrementYRegister,
+        &[Operation::new(0xc8, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::increment_y_register,
+    ),
+    OperationCodes::new(
+        OperationName::IncrementMemory,
+        &[
+            Operation::new(0xe6, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0xf6, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0xee, 3, 6, AddressingMode::Absolute),
+            Operation::new(0xfe, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::increment_memory,
+    ),
+    OperationCodes::new(
+        OperationName::Jump,
+        &[
+            Operation::new(0x4c, 3, 3, AddressingMode::Absolute),
+            Operation::new(0x6c, 3, 5, AddressingMode::Indirect),
+        ],
+        cpu_functions::jump,
+    ),
+    OperationCodes::new(
+        OperationName::JumpToSubroutine,
+        &[Operation::new(0x20, 3, 6, AddressingMode::Absolute)],
+        cpu_functions::jump_to_subroutine,
+    ),
+    OperationCodes::new(
+        OperationName::DecrementXRegister,
+        &[Operation::new(0xca, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::decrement_x_register,
+    ),
+    OperationCodes::new(
+        OperationName::DecrementYRegister,
+        &[Operation::new(0x88, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::decrement_y_register,
+    ),
+    OperationCodes::new(
+        OperationName::DecrementMemory,
+        &[
+            Operation::new(0xc6, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0xd6, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0xce, 3, 6, AddressingMode::Absolute),
+            Operation::new(0xde, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::decrement_memory,
+    ),
+    OperationCodes::new(
+        OperationName::LoadAccumulator,
+        &[
+            Operation::new(0xa9, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xa5, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xb5, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xad, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xbd, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0xb9, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0xa1, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0xb1, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::load_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::LoadXRegister,
+        &[
+            Operation::new(0xA2, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xA6, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xB6, 2, 4, AddressingMode::ZeroPage_Y),
+            Operation::new(0xAE, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xBE, 3, 4, AddressingMode::Absolute_Y),
+        ],
+        cpu_functions::load_x_register,
+    ),
+    OperationCodes::new(
+        OperationName::LoadYRegister,
+        &[
+            Operation::new(0xA0, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xA4, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xB4, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xAC, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xBC, 3, 4, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::load_y_register,
+    ),
+    OperationCodes::new(
+        OperationName::ReturnFromInterrupt,
+        &[Operation::new(0x40, 1, 6, AddressingMode::Implied)],
+        cpu_functions::return_from_interrupt,
+    ),
+    OperationCodes::new(
+        OperationName::ReturnFromSubroutine,
+        &[Operation::new(0x60, 1, 6, AddressingMode::Implied)],
+        cpu_functions::return_from_subroutine,
+    ),
+    OperationCodes::new(
+        OperationName::StoreAccumulator,
+        &[
+            Operation::new(0x85, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x95, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x8d, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x9d, 3, 5, AddressingMode::Absolute_X),
+            Operation::new(0x99, 3, 5, AddressingMode::Absolute_Y),
+            Operation::new(0x81, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0x91, 2, 6, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::store_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::StoreXRegister,
+        &[
+            Operation::new(0x86, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x96, 2, 4, AddressingMode::ZeroPage_Y),
+            Operation::new(0x8e, 3, 4, AddressingMode::Absolute),
+        ],
+        cpu_functions::store_x_register,
+    ),
+    OperationCodes::new(
+        OperationName::StoreYRegister,
+        &[
+            Operation::new(0x84, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x94, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x8c, 3, 4, AddressingMode::Absolute),
+        ],
+        cpu_functions::store_y_register,
+    ),
+    OperationCodes::new(
+        OperationName::Compare,
+        &[
+            Operation::new(0xC9, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xC5, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xD5, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xCD, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xDD, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0xD9, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0xC1, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0xD1, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::compare_a,
+    ),
+    OperationCodes::new(
+        OperationName::CompareX,
+        &[
+            Operation::new(0xE0, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xE4, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xEC, 3, 4, AddressingMode::Absolute),
+        ],
+        cpu_functions::compare_x,
+    ),
+    OperationCodes::new(
+        OperationName::CompareY,
+        &[
+            Operation::new(0xC0, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xC4, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xCC, 3, 4, AddressingMode::Absolute),
+        ],
+        cpu_functions::compare_y,
+    ),
+    OperationCodes::new(
+        OperationName::AddWithCarry,
+        &[
+            Operation::new(0x69, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x65, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x75, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x6d, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x7d, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x79, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0x61, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0x71, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::add_with_carry,
+    ),
+    OperationCodes::new(
+        OperationName::SubstractWithCarry,
+        &[
+            Operation::new(0xe9, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xe5, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xf5, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xed, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xfd, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0xf9, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0xe1, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0xf1, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::substract_with_carry,
+    ),
+    OperationCodes::new(
+        OperationName::TransferStackPointerToX,
+        &[Operation::new(0xba, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_stack_pointer_to_x,
+    ),
+    OperationCodes::new(
+        OperationName::TransferXToAccumulator,
+        &[Operation::new(0x8a, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_x_to_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::TransferXToStackPointer,
+        &[Operation::new(0x9a, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_x_to_stack_pointer,
+    ),
+    OperationCodes::new(
+        OperationName::TransferYToAccumulator,
+        &[Operation::new(0x98, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::transfer_y_to_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::ArithmeticShiftLeft,
+        &[
+            Operation::new(0x06, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x16, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x0e, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x1e, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::arithmetic_shift_left,
+    ),
+    OperationCodes::new(
+        OperationName::ArithmeticShiftLeft,
+        &[Operation::new(0x0a, 1, 2, AddressingMode::Accumulator)],
+        cpu_functions::arithmetic_shift_left_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::BitTest,
+        &[
+            Operation::new(0x24, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x2c, 3, 4, AddressingMode::Absolute),
+        ],
+        cpu_functions::bit_test,
+    ),
+    OperationCodes::new(
+        OperationName::ClearCarryFlag,
+        &[Operation::new(0x18, 1, 2, AddressingMode::Implied)],
+        cpu_functions::clear_carry_flag,
+    ),
+    OperationCodes::new(
+        OperationName::ClearDecimalMode,
+        &[Operation::new(0xd8, 1, 2, AddressingMode::Implied)],
+        cpu_functions::clear_decimal_mode,
+    ),
+    OperationCodes::new(
+        OperationName::ClearInterruptDisable,
+        &[Operation::new(0x58, 1, 2, AddressingMode::Implied)],
+        cpu_functions::clear_interrupt_disable,
+    ),
+    OperationCodes::new(
+        OperationName::ClearOverflowFlag,
+        &[Operation::new(0xb8, 1, 2, AddressingMode::Implied)],
+        cpu_functions::clear_overflow_flag,
+    ),
+    OperationCodes::new(
+        OperationName::ExclusiveOR,
+        &[
+            Operation::new(0x49, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x45, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x55, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x4d, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x5d, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x59, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0x41, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0x51, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::exclusive_or,
+    ),
+    OperationCodes::new(
+        OperationName::LogicalAND,
+        &[
+            Operation::new(0x29, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x25, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x35, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x2d, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x3d, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x39, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0x21, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0x31, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::logical_and,
+    ),
+    OperationCodes::new(
+        OperationName::LogicalInclusiveOR,
+        &[
+            Operation::new(0x09, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x05, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x15, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x0d, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x1d, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x19, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0x01, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0x11, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::logical_inclusive_or,
+    ),
+    OperationCodes::new(
+        OperationName::LogicalShiftRight,
+        &[
+            Operation::new(0x46, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x56, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x4e, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x5e, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::logical_shift_right,
+    ),
+    OperationCodes::new(
+        OperationName::LogicalShiftRight,
+        &[Operation::new(0x4a, 1, 2, AddressingMode::Accumulator)],
+        cpu_functions::logical_shift_right_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::PullAccumulator,
+        &[Operation::new(0x68, 1, 4, AddressingMode::Implied)],
+        cpu_functions::pull_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::PullProcessorStatus,
+        &[Operation::new(0x28, 1, 4, AddressingMode::Implied)],
+        cpu_functions::pull_processor_status,
+    ),
+    OperationCodes::new(
+        OperationName::PushAccumulator,
+        &[Operation::new(0x48, 1, 3, AddressingMode::Implied)],
+        cpu_functions::push_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::PushProcessorStatus,
+        &[Operation::new(0x08, 1, 3, AddressingMode::Implied)],
+        cpu_functions::push_processor_status,
+    ),
+    OperationCodes::new(
+        OperationName::RotateLeft,
+        &[
+            Operation::new(0x26, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x36, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x2e, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x3e, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::rotate_left,
+    ),
+    OperationCodes::new(
+        OperationName::RotateLeft,
+        &[Operation::new(0x2a, 1, 2, AddressingMode::Accumulator)],
+        cpu_functions::rotate_left_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::RotateRight,
+        &[
+            Operation::new(0x66, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x76, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x6e, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x7e, 3, 7, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::rotate_right,
+    ),
+    OperationCodes::new(
+        OperationName::RotateRight,
+        &[Operation::new(0x6a, 1, 2, AddressingMode::Accumulator)],
+        cpu_functions::rotate_right_accumulator,
+    ),
+    OperationCodes::new(
+        OperationName::SetCarryFlag,
+        &[Operation::new(0x38, 1, 2, AddressingMode::Implied)],
+        cpu_functions::set_carry_flag,
+    ),
+    OperationCodes::new(
+        OperationName::SetDecimalFlag,
+        &[Operation::new(0xf8, 1, 2, AddressingMode::Implied)],
+        cpu_functions::set_decimal_flag,
+    ),
+    OperationCodes::new(
+        OperationName::SetInterruptDisable,
+        &[Operation::new(0x78, 1, 2, AddressingMode::Implied)],
+        cpu_functions::set_interrupt_disable,
+    ),
+    OperationCodes::new(
+        OperationName::NoOperation,
+        &[Operation::new(0xea, 1, 2, AddressingMode::NoneAddressing)],
+        cpu_functions::no_operation,
+    ),
+    OperationCodes::new(
+        OperationName::UnofficialNoOperation,
+        &[
+            Operation::new(0x1a, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x3a, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x5a, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x7a, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0xda, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0xfa, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x80, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x82, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x89, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xc2, 2, 2, AddressingMode::Immediate),
+            Operation::new(0xe2, 2, 2, AddressingMode::Immediate),
+            Operation::new(0x04, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x44, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x64, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x14, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x34, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x54, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x74, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xd4, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0xf4, 2, 4, AddressingMode::ZeroPage_X),
+            Operation::new(0x0c, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x1c, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x3c, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x5c, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0x7c, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0xdc, 3, 4, AddressingMode::Absolute_X),
+            Operation::new(0xfc, 3, 4, AddressingMode::Absolute_X),
+        ],
+        cpu_functions::no_operation,
+    ),
+    OperationCodes::new(
+        OperationName::LoadAccumulatorAndX,
+        &[
+            Operation::new(0xa7, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0xb7, 2, 4, AddressingMode::ZeroPage_Y),
+            Operation::new(0xaf, 3, 4, AddressingMode::Absolute),
+            Operation::new(0xbf, 3, 4, AddressingMode::Absolute_Y),
+            Operation::new(0xa3, 2, 6, AddressingMode::Indirect_X),
+            Operation::new(0xb3, 2, 5, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::load_accumulator_and_x,
+    ),
+    OperationCodes::new(
+        OperationName::StoreAccumulatorAndX,
+        &[
+            Operation::new(0x87, 2, 3, AddressingMode::ZeroPage),
+            Operation::new(0x97, 2, 4, AddressingMode::ZeroPage_Y),
+            Operation::new(0x8f, 3, 4, AddressingMode::Absolute),
+            Operation::new(0x83, 2, 6, AddressingMode::Indirect_X),
+        ],
+        cpu_functions::store_accumulator_and_x,
+    ),
+    OperationCodes::new(
+        OperationName::DecrementAndCompare,
+        &[
+            Operation::new(0xc7, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0xd7, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0xcf, 3, 6, AddressingMode::Absolute),
+            Operation::new(0xdf, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0xdb, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0xc3, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0xd3, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::decrement_and_compare,
+    ),
+    OperationCodes::new(
+        OperationName::IncrementAndSubstract,
+        &[
+            Operation::new(0xe7, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0xf7, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0xef, 3, 6, AddressingMode::Absolute),
+            Operation::new(0xff, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0xfb, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0xe3, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0xf3, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::increment_and_substract,
+    ),
+    OperationCodes::new(
+        OperationName::ShiftLeftAndOR,
+        &[
+            Operation::new(0x07, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x17, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x0f, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x1f, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0x1b, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0x03, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0x13, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::shift_left_and_or,
+    ),
+    OperationCodes::new(
+        OperationName::RotateLeftAndAND,
+        &[
+            Operation::new(0x27, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x37, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x2f, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x3f, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0x3b, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0x23, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0x33, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::rotate_left_and_and,
+    ),
+    OperationCodes::new(
+        OperationName::ShiftRightAndExclusiveOR,
+        &[
+            Operation::new(0x47, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x57, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x4f, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x5f, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0x5b, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0x43, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0x53, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::shift_right_and_exclusive_or,
+    ),
+    OperationCodes::new(
+        OperationName::RotateRightAndAdd,
+        &[
+            Operation::new(0x67, 2, 5, AddressingMode::ZeroPage),
+            Operation::new(0x77, 2, 6, AddressingMode::ZeroPage_X),
+            Operation::new(0x6f, 3, 6, AddressingMode::Absolute),
+            Operation::new(0x7f, 3, 7, AddressingMode::Absolute_X),
+            Operation::new(0x7b, 3, 7, AddressingMode::Absolute_Y),
+            Operation::new(0x63, 2, 8, AddressingMode::Indirect_X),
+            Operation::new(0x73, 2, 8, AddressingMode::Indirect_Y),
+        ],
+        cpu_functions::rotate_right_and_add,
+    ),
+    OperationCodes::new(
+        OperationName::UnofficialSubstractWithCarry,
+        &[Operation::new(0xeb, 2, 2, AddressingMode::Immediate)],
+        cpu_functions::substract_with_carry,
+    ),
+    OperationCodes::new(
+        OperationName::Jam,
+        &[
+            Operation::new(0x02, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x12, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x22, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x32, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x42, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x52, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x62, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x72, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0x92, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0xb2, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0xd2, 1, 2, AddressingMode::NoneAddressing),
+            Operation::new(0xf2, 1, 2, AddressingMode::NoneAddressing),
+        ],
+        cpu_functions::jam,
+    ),
+];
+
+/// Decoded entry of the flat dispatch table, indexed by operation code.
+#[derive(Clone, Copy)]
+pub struct OpEntry {
+    pub operation_name: OperationName,
+    pub operation: &'static Operation,
+    pub execute_function: ExecuteFunction,
+}
+
+pub static OPERATION_TABLE: [Option<OpEntry>; 256] = build_operation_table(CPU_OPS_CODES);
+
+// Evaluated at compile time, so a duplicated operation code fails the build
+const fn build_operation_table(
+    operation_codes: &'static [OperationCodes],
+) -> [Option<OpEntry>; 256] {
+    let mut table: [Option<OpEntry>; 256] = [None; 256];
+    let mut i = 0;
+    while i < operation_codes.len() {
+        let cpu_operation = &operation_codes[i];
+        let mut j = 0;
+        while j < cpu_operation.operations.len() {
+            let cpu_op = &cpu_operation.operations[j];
+            if table[cpu_op.operation_code as usize].is_some() {
+                panic!("Operation code registered twice in CPU_OPS_CODES");
+            }
+            table[cpu_op.operation_code as usize] = Some(OpEntry {
+                operation_name: cpu_operation.operation_name,
+                operation: cpu_op,
+                execute_function: cpu_operation.execute_function,
+            });
+            j += 1;
+        }
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Documented 6502 opcode matrix; "---" marks the unstable opcodes left unimplemented
+    const OPCODE_MATRIX: [[&str; 16]; 16] = [
+        [
+            "BRK imp", "ORA izx", "JAM imp", "SLO izx", "NOP zp", "ORA zp", "ASL zp", "SLO zp",
+            "PHP imp", "ORA imm", "ASL acc", "---", "NOP abs", "ORA abs", "ASL abs", "SLO abs",
+        ],
+        [
+            "BPL rel", "ORA izy", "JAM imp", "SLO izy", "NOP zpx", "ORA zpx", "ASL zpx", "SLO zpx",
+            "CLC imp", "ORA aby", "NOP imp", "SLO aby", "NOP abx", "ORA abx", "ASL abx", "SLO abx",
+        ],
+        [
+            "JSR abs", "AND izx", "JAM imp", "RLA izx", "BIT zp", "AND zp", "ROL zp", "RLA zp",
+            "PLP imp", "AND imm", "ROL acc", "---", "BIT abs", "AND abs", "ROL abs", "RLA abs",
+        ],
+        [
+            "BMI rel", "AND izy", "JAM imp", "RLA izy", "NOP zpx", "AND zpx", "ROL zpx", "RLA zpx",
+            "SEC imp", "AND aby", "NOP imp", "RLA aby", "NOP abx", "AND abx", "ROL abx", "RLA abx",
+        ],
+        [
+            "RTI imp", "EOR izx", "JAM imp", "SRE izx", "NOP zp", "EOR zp", "LSR zp", "SRE zp",
+            "PHA imp", "EOR imm", "LSR acc", "---", "JMP abs", "EOR abs", "LSR abs", "SRE abs",
+        ],
+        [
+            "BVC rel", "EOR izy", "JAM imp", "SRE izy", "NOP zpx", "EOR zpx", "LSR zpx", "SRE zpx",
+            "CLI imp", "EOR aby", "NOP imp", "SRE aby", "NOP abx", "EOR abx", "LSR abx", "SRE abx",
+        ],
+        [
+            "RTS imp", "ADC izx", "JAM imp", "RRA izx", "NOP zp", "ADC zp", "ROR zp", "RRA zp",
+            "PLA imp", "ADC imm", "ROR acc", "---", "JMP ind", "ADC abs", "ROR abs", "RRA abs",
+        ],
+        [
+            "BVS rel", "ADC izy", "JAM imp", "RRA izy", "NOP zpx", "ADC zpx", "ROR zpx", "RRA zpx",
+            "SEI imp", "ADC aby", "NOP imp", "RRA aby", "NOP abx", "ADC abx", "ROR abx", "RRA abx",
+        ],
+        [
+            "NOP imm", "STA izx", "NOP imm", "SAX izx", "STY zp", "STA zp", "STX zp", "SAX zp",
+            "DEY imp", "NOP imm", "TXA imp", "---", "STY abs", "STA abs", "STX abs", "SAX abs",
+        ],
+        [
+            "BCC rel", "STA izy", "JAM imp", "---", "STY zpx", "STA zpx", "STX zpy", "SAX zpy",
+            "TYA imp", "STA aby", "TXS imp", "---", "---", "STA abx", "---", "---",
+        ],
+        [
+            "LDY imm", "LDA izx", "LDX imm", "LAX izx", "LDY zp", "LDA zp", "LDX zp", "LAX zp",
+            "TAY imp", "LDA imm", "TAX imp", "---", "LDY abs", "LDA abs", "LDX abs", "LAX abs",
+        ],
+        [
+            "BCS rel", "LDA izy", "JAM imp", "LAX izy", "LDY zpx", "LDA zpx", "LDX zpy", "LAX zpy",
+            "CLV imp", "LDA aby", "TSX imp", "---", "LDY abx", "LDA abx", "LDX aby", "LAX aby",
+        ],
+        [
+            "CPY imm", "CMP izx", "NOP imm", "DCP izx", "CPY zp", "CMP zp", "DEC zp", "DCP zp",
+            "INY imp", "CMP imm", "DEX imp", "---", "CPY abs", "CMP abs", "DEC abs", "DCP abs",
+        ],
+        [
+            "BNE rel", "CMP izy", "JAM imp", "DCP izy", "NOP zpx", "CMP zpx", "DEC zpx", "DCP zpx",
+            "CLD imp", "CMP aby", "NOP imp", "DCP aby", "NOP abx", "CMP abx", "DEC abx", "DCP abx",
+        ],
+        [
+            "CPX imm", "SBC izx", "NOP imm", "ISB izx", "CPX zp", "SBC zp", "INC zp", "ISB zp",
+            "INX imp", "SBC imm", "NOP imp", "SBC imm", "CPX abs", "SBC abs", "INC abs", "ISB abs",
+        ],
+        [
+            "BEQ rel", "SBC izy", "JAM imp", "ISB izy", "NOP zpx", "SBC zpx", "INC zpx", "ISB zpx",
+            "SED imp", "SBC aby", "NOP imp", "ISB aby", "NOP abx", "SBC abx", "INC abx", "ISB abx",
+        ],
     ];
-    pub static ref OPERATION_CODES_MAP: HashMap<u8, (&'static Operation, ExecuteFunction)> = {
-        let mut map = HashMap::new();
-        for cpu_operation in &*CPU_OPS_CODES {
-            for cpu_op in &*cpu_operation.operations {
-                map.insert(
-                    cpu_op.operation_code,
-                    (cpu_op, cpu_operation.execute_function),
-                );
+
+    fn expected_mode_and_len(mode: &str) -> (&'static [AddressingMode], u8) {
+        match mode {
+            "imp" => (
+                &[AddressingMode::Implied, AddressingMode::NoneAddressing],
+                1,
+            ),
+            "acc" => (&[AddressingMode::Accumulator], 1),
+            "imm" => (&[AddressingMode::Immediate], 2),
+            "zp" => (&[AddressingMode::ZeroPage], 2),
+            "zpx" => (&[AddressingMode::ZeroPage_X], 2),
+            "zpy" => (&[AddressingMode::ZeroPage_Y], 2),
+            "izx" => (&[AddressingMode::Indirect_X], 2),
+            "izy" => (&[AddressingMode::Indirect_Y], 2),
+            "rel" => (&[AddressingMode::Relative], 2),
+            "abs" => (&[AddressingMode::Absolute], 3),
+            "abx" => (&[AddressingMode::Absolute_X], 3),
+            "aby" => (&[AddressingMode::Absolute_Y], 3),
+            "ind" => (&[AddressingMode::Indirect], 3),
+            _ => panic!("unknown addressing mode {}", mode),
+        }
+    }
+
+    #[test]
+    fn test_operation_table_matches_opcode_matrix() {
+        for (code, entry) in OPERATION_TABLE.iter().enumerate() {
+            let cell = OPCODE_MATRIX[code >> 4][code & 0x0F];
+            if cell == "---" {
+                assert!(entry.is_none(), "{:02x} should not be implemented", code);
+                continue;
             }
+            let entry = entry.unwrap_or_else(|| panic!("{:02x} ({}) is missing", code, cell));
+            let (mnemonic, mode) = cell.split_once(' ').unwrap();
+            let (modes, len) = expected_mode_and_len(mode);
+
+            assert_eq!(entry.operation.operation_code as usize, code);
+            assert_eq!(entry.operation_name.mnemonic(), mnemonic, "{:02x}", code);
+            assert!(
+                modes.contains(&entry.operation.addressing_mode),
+                "{:02x} ({}) has mode {:?}",
+                code,
+                cell,
+                entry.operation.addressing_mode
+            );
+            assert_eq!(entry.operation.len, len, "{:02x} ({}) length", code, cell);
         }
-        map
-    };
+    }
+
+    #[test]
+    fn test_operation_table_has_no_duplicates() {
+        let registered: usize = CPU_OPS_CODES.iter().map(|op| op.operations.len()).sum();
+        let unique = OPERATION_TABLE
+            .iter()
+            .filter(|entry| entry.is_some())
+            .count();
+        assert_eq!(registered, unique);
+    }
 }
@@ -0,0 +1,15 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::interrupt::Interrupt;
+
+/// Summary of a single instruction executed by `CPU::step`.
+///
+/// When `interrupt` is set the step serviced a hardware interrupt instead of
+/// executing an instruction; like the real 6502 it reports a forced BRK opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepResult {
+    pub operation_code: u8,
+    pub addressing_mode: AddressingMode,
+    pub cycles: u8,
+    pub program_counter: u16,
+    pub interrupt: Option<Interrupt>,
+}
@@ -0,0 +1,236 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::cpu_model::CPU;
+use crate::cpu::disassembler::format_operand;
+use crate::cpu::operation_codes::{OperationName, OPERATION_TABLE};
+use std::io::{self, Write};
+
+const PPU_DOTS_PER_CPU_CYCLE: u64 = 3;
+const PPU_DOTS_PER_SCANLINE: u64 = 341;
+const PPU_SCANLINES_PER_FRAME: u64 = 262;
+
+// Buses that cannot peek without side effects show up as zeros
+fn read(cpu: &CPU, address: u16) -> u8 {
+    cpu.bus.peek(address).unwrap_or(0)
+}
+
+fn read_zero_page_u16(cpu: &CPU, pointer: u8) -> u16 {
+    (read(cpu, pointer.wrapping_add(1) as u16) as u16) << 8 | read(cpu, pointer as u16) as u16
+}
+
+// The "@ address = value" annotations nestest.log shows after each operand
+fn annotate(cpu: &CPU, name: OperationName, mode: &AddressingMode, operand: &[u8]) -> String {
+    let byte = || operand[0];
+    let word = || (operand[1] as u16) << 8 | operand[0] as u16;
+    match mode {
+        AddressingMode::ZeroPage => format!(" = {:02X}", read(cpu, byte() as u16)),
+        AddressingMode::ZeroPage_X | AddressingMode::ZeroPage_Y => {
+            let index = match mode {
+                AddressingMode::ZeroPage_X => cpu.register_x,
+                _ => cpu.register_y,
+            };
+            let address = byte().wrapping_add(index);
+            format!(" @ {:02X} = {:02X}", address, read(cpu, address as u16))
+        }
+        AddressingMode::Absolute => match name {
+            OperationName::Jump | OperationName::JumpToSubroutine => String::new(),
+            _ => format!(" = {:02X}", read(cpu, word())),
+        },
+        AddressingMode::Absolute_X | AddressingMode::Absolute_Y => {
+            let index = match mode {
+                AddressingMode::Absolute_X => cpu.register_x,
+                _ => cpu.register_y,
+            };
+            let address = word().wrapping_add(index as u16);
+            format!(" @ {:04X} = {:02X}", address, read(cpu, address))
+        }
+        AddressingMode::Indirect => {
+            // Same page-wrap bug as the JMP implementation
+            let pointer = word();
+            let hi_pointer = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+            let target = (read(cpu, hi_pointer) as u16) << 8 | read(cpu, pointer) as u16;
+            format!(" = {:04X}", target)
+        }
+        AddressingMode::Indirect_X => {
+            let pointer = byte().wrapping_add(cpu.register_x);
+            let address = read_zero_page_u16(cpu, pointer);
+            format!(
+                " @ {:02X} = {:04X} = {:02X}",
+                pointer,
+                address,
+                read(cpu, address)
+            )
+        }
+        AddressingMode::Indirect_Y => {
+            let base = read_zero_page_u16(cpu, byte());
+            let address = base.wrapping_add(cpu.register_y as u16);
+            format!(
+                " = {:04X} @ {:04X} = {:02X}",
+                base,
+                address,
+                read(cpu, address)
+            )
+        }
+        _ => String::new(),
+    }
+}
+
+/// Formats the instruction at the program counter as a nestest.log line.
+///
+/// The PPU column is derived from the CPU cycle count, which matches nestest
+/// as long as rendering stays disabled.
+pub fn trace(cpu: &CPU) -> String {
+    let address = cpu.program_counter;
+    let code = read(cpu, address);
+
+    let (bytes, mnemonic, disassembly) = match OPERATION_TABLE[code as usize] {
+        Some(entry) => {
+            let operation = entry.operation;
+            let operand: Vec<u8> = (1..operation.len as u16)
+                .map(|offset| read(cpu, address.wrapping_add(offset)))
+                .collect();
+            let mnemonic = match entry.operation_name.is_unofficial() {
+                true => format!("*{}", entry.operation_name.mnemonic()),
+                false => entry.operation_name.mnemonic().to_string(),
+            };
+            let disassembly = format!(
+                "{}{}",
+                format_operand(&operation.addressing_mode, address, &operand),
+                annotate(
+                    cpu,
+                    entry.operation_name,
+                    &operation.addressing_mode,
+                    &operand
+                )
+            );
+            let mut bytes = vec![code];
+            bytes.extend(operand);
+            (bytes, mnemonic, disassembly)
+        }
+        None => (vec![code], String::from(".byte"), format!("${:02X}", code)),
+    };
+
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    let instruction = format!(
+        "{:04X}  {:8} {: >4} {}",
+        address,
+        hex.join(" "),
+        mnemonic,
+        disassembly
+    );
+
+    let dots = cpu.cycles * PPU_DOTS_PER_CPU_CYCLE;
+    format!(
+        "{:47} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} PPU:{:>3},{:>3} CYC:{}",
+        instruction.trim_end(),
+        cpu.register_a,
+        cpu.register_x,
+        cpu.register_y,
+        cpu.status,
+        cpu.stack_pointer,
+        (dots / PPU_DOTS_PER_SCANLINE) % PPU_SCANLINES_PER_FRAME,
+        dots % PPU_DOTS_PER_SCANLINE,
+        cpu.cycles
+    )
+}
+
+/// Writes one nestest.log line per instruction to any `io::Write`.
+pub struct TraceLogger<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> TraceLogger<W> {
+    pub fn new(writer: W) -> Self {
+        TraceLogger { writer }
+    }
+
+    /// Logs the instruction the CPU is about to execute; call it before `CPU::step`.
+    pub fn log(&mut self, cpu: &CPU) -> io::Result<()> {
+        writeln!(self.writer, "{}", trace(cpu))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::memory::Memory;
+
+    fn create_trace_cpu(program: &[u8]) -> CPU {
+        let mut memory = Memory::new();
+        memory.memory[0xC000..0xC000 + program.len()].copy_from_slice(program);
+        let mut cpu = CPU::with_bus(Box::new(memory));
+        cpu.program_counter = 0xC000;
+        cpu.status = 0x24;
+        cpu.cycles = 7;
+        cpu
+    }
+
+    #[test]
+    fn test_trace_nestest_first_line() {
+        let cpu = create_trace_cpu(&[0x4c, 0xf5, 0xc5]);
+        assert_eq!(
+            trace(&cpu),
+            "C000  4C F5 C5  JMP $C5F5                       \
+             A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7"
+        );
+    }
+
+    #[test]
+    fn test_trace_zero_page_and_immediate() {
+        let mut cpu = create_trace_cpu(&[0x86, 0x00]);
+        cpu.bus.write(0x00, 0x12);
+        assert!(trace(&cpu).starts_with("C000  86 00     STX $00 = 12 "));
+
+        let cpu = create_trace_cpu(&[0xa2, 0x00]);
+        assert!(trace(&cpu).starts_with("C000  A2 00     LDX #$00 "));
+    }
+
+    #[test]
+    fn test_trace_indirect_modes() {
+        let mut cpu = create_trace_cpu(&[0xa1, 0x80]);
+        cpu.bus.write(0x80, 0x00);
+        cpu.bus.write(0x81, 0x02);
+        cpu.bus.write(0x0200, 0x5a);
+        assert!(trace(&cpu).starts_with("C000  A1 80     LDA ($80,X) @ 80 = 0200 = 5A "));
+
+        let mut cpu = create_trace_cpu(&[0xb1, 0x89]);
+        cpu.register_y = 0x34;
+        cpu.bus.write(0x89, 0x00);
+        cpu.bus.write(0x8a, 0x03);
+        cpu.bus.write(0x0334, 0x89);
+        assert!(trace(&cpu).starts_with("C000  B1 89     LDA ($89),Y = 0300 @ 0334 = 89 "));
+
+        let mut cpu = create_trace_cpu(&[0x6c, 0xff, 0x02]);
+        cpu.bus.write(0x02ff, 0x7e);
+        cpu.bus.write(0x0200, 0xdb);
+        assert!(trace(&cpu).starts_with("C000  6C FF 02  JMP ($02FF) = DB7E "));
+    }
+
+    #[test]
+    fn test_trace_unofficial_marker_and_accumulator() {
+        let cpu = create_trace_cpu(&[0x04, 0xa9]);
+        assert!(trace(&cpu).starts_with("C000  04 A9    *NOP $A9 = 00 "));
+
+        let cpu = create_trace_cpu(&[0x4a]);
+        assert!(trace(&cpu).starts_with("C000  4A        LSR A "));
+    }
+
+    #[test]
+    fn test_trace_logger_writes_lines() {
+        let mut cpu = create_trace_cpu(&[0xe8, 0xe8]);
+        let mut logger = TraceLogger::new(Vec::new());
+
+        logger.log(&cpu).unwrap();
+        cpu.step().unwrap();
+        logger.log(&cpu).unwrap();
+
+        let output = String::from_utf8(logger.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("C001  E8        INX "));
+        assert!(lines[1].ends_with("PPU:  0, 27 CYC:9"));
+    }
+}
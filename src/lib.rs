@@ -1 +1,3 @@
+pub mod cartridge;
 pub mod cpu;
+pub mod ppu;
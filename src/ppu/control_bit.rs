@@ -0,0 +1,13 @@
+/// Bits of PPUCTRL ($2000).
+#[derive(Debug)]
+#[repr(u8)]
+pub enum ControlBit {
+    NametableX = 0,
+    NametableY = 1,
+    VramIncrement = 2,
+    SpritePatternTable = 3,
+    BackgroundPatternTable = 4,
+    SpriteSize = 5,
+    MasterSlave = 6,
+    GenerateNmi = 7,
+}
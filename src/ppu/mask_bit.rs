@@ -0,0 +1,13 @@
+/// Bits of PPUMASK ($2001).
+#[derive(Debug)]
+#[repr(u8)]
+pub enum MaskBit {
+    Greyscale = 0,
+    ShowBackgroundLeft = 1,
+    ShowSpritesLeft = 2,
+    ShowBackground = 3,
+    ShowSprites = 4,
+    EmphasizeRed = 5,
+    EmphasizeGreen = 6,
+    EmphasizeBlue = 7,
+}
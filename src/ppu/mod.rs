@@ -0,0 +1,7 @@
+pub mod control_bit;
+pub mod mask_bit;
+pub mod ppu_memory;
+pub mod ppu_model;
+pub mod ppu_registers;
+pub mod ppu_rendering;
+pub mod status_bit;
@@ -0,0 +1,100 @@
+use crate::cartridge::mapper::Mapper;
+use crate::cartridge::mirroring::Mirroring;
+use crate::ppu::ppu_model::PPU;
+
+pub const PPU_ADDRESS_MASK: u16 = 0x3FFF;
+const PATTERN_TABLES_END: u16 = 0x1FFF;
+const NAMETABLES_START: u16 = 0x2000;
+const NAMETABLES_END: u16 = 0x3EFF;
+pub const PALETTE_START: u16 = 0x3F00;
+const NAMETABLE_SIZE: u16 = 0x0400;
+
+/// Where a logical nametable address lands in VRAM under `mirroring`.
+pub fn nametable_index(address: u16, mirroring: Mirroring) -> usize {
+    let offset = (address - NAMETABLES_START) % (4 * NAMETABLE_SIZE);
+    let table = offset / NAMETABLE_SIZE;
+    let physical_table = match mirroring {
+        Mirroring::Horizontal => table / 2,
+        Mirroring::Vertical => table % 2,
+        Mirroring::SingleScreenLower => 0,
+        Mirroring::SingleScreenUpper => 1,
+        Mirroring::FourScreen => table,
+    };
+    (physical_table * NAMETABLE_SIZE + offset % NAMETABLE_SIZE) as usize
+}
+
+// $3F10/$3F14/$3F18/$3F1C are the sprite palettes' copies of the backdrop entries
+fn palette_index(address: u16) -> usize {
+    let index = (address as usize) & 0x1F;
+    match index {
+        0x10 | 0x14 | 0x18 | 0x1C => index - 0x10,
+        _ => index,
+    }
+}
+
+impl PPU {
+    /// Reads the PPU address space: pattern tables from the cartridge, nametables
+    /// from VRAM and palette RAM.
+    pub fn read_memory(&mut self, address: u16, cartridge: &mut dyn Mapper) -> u8 {
+        let address = address & PPU_ADDRESS_MASK;
+        cartridge.ppu_address(address);
+        match address {
+            0..=PATTERN_TABLES_END => cartridge.ppu_read(address),
+            NAMETABLES_START..=NAMETABLES_END => {
+                self.vram[nametable_index(address, cartridge.mirroring())]
+            }
+            _ => self.palette[palette_index(address)],
+        }
+    }
+
+    pub fn write_memory(&mut self, address: u16, value: u8, cartridge: &mut dyn Mapper) {
+        let address = address & PPU_ADDRESS_MASK;
+        cartridge.ppu_address(address);
+        match address {
+            0..=PATTERN_TABLES_END => cartridge.ppu_write(address, value),
+            NAMETABLES_START..=NAMETABLES_END => {
+                self.vram[nametable_index(address, cartridge.mirroring())] = value
+            }
+            _ => self.palette[palette_index(address)] = value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom::nrom;
+
+    #[test]
+    fn test_nametable_mirroring() {
+        for (mirroring, expected) in [
+            (Mirroring::Horizontal, [0, 0, 1, 1]),
+            (Mirroring::Vertical, [0, 1, 0, 1]),
+            (Mirroring::SingleScreenLower, [0, 0, 0, 0]),
+            (Mirroring::SingleScreenUpper, [1, 1, 1, 1]),
+            (Mirroring::FourScreen, [0, 1, 2, 3]),
+        ] {
+            for (table, physical) in expected.iter().enumerate() {
+                let address = 0x2000 + table as u16 * 0x400 + 0x12;
+                assert_eq!(nametable_index(address, mirroring), physical * 0x400 + 0x12);
+            }
+        }
+        // $3000-$3EFF mirrors $2000-$2EFF
+        assert_eq!(nametable_index(0x3412, Mirroring::Vertical), 0x412);
+    }
+
+    #[test]
+    fn test_memory_routes_to_cartridge_vram_and_palette() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0b0000_0001);
+
+        ppu.write_memory(0x1234, 0x11, &mut nrom);
+        ppu.write_memory(0x2C05, 0x22, &mut nrom);
+        ppu.write_memory(0x3F10, 0x33, &mut nrom);
+
+        assert_eq!(nrom.ppu_read(0x1234), 0x11);
+        assert_eq!(ppu.read_memory(0x2405, &mut nrom), 0x22);
+        assert_eq!(ppu.read_memory(0x3F00, &mut nrom), 0x33);
+        assert_eq!(ppu.read_memory(0x7F20, &mut nrom), 0x33);
+    }
+}
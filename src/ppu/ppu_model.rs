@@ -0,0 +1,59 @@
+pub const OAM_SIZE: usize = 0x100;
+// Two physical nametables on the console, four when the cartridge adds its own RAM
+pub const VRAM_SIZE: usize = 0x1000;
+pub const PALETTE_SIZE: usize = 0x20;
+pub const FRAME_WIDTH: usize = 256;
+pub const FRAME_HEIGHT: usize = 240;
+
+/// The picture processing unit's registers and internal memory.
+///
+/// Scrolling follows the loopy model: `vram_address` (v) is the current VRAM
+/// address, `temporary_address` (t) the one being assembled by PPUSCROLL and
+/// PPUADDR, `fine_x_scroll` (x) the pixel offset within a tile and `write_toggle`
+/// (w) which half of a two-write register comes next. Pattern tables and
+/// nametable mirroring come from the cartridge passed into each access.
+pub struct PPU {
+    pub control: u8,
+    pub mask: u8,
+    pub status: u8,
+    pub oam_address: u8,
+    pub oam: [u8; OAM_SIZE],
+    pub vram: [u8; VRAM_SIZE],
+    pub palette: [u8; PALETTE_SIZE],
+    pub vram_address: u16,
+    pub temporary_address: u16,
+    pub fine_x_scroll: u8,
+    pub write_toggle: bool,
+    pub read_buffer: u8,
+    // The last value driven onto the PPU's data bus, returned by write-only registers
+    pub open_bus: u8,
+    // One NES master palette index (0-63) per pixel, row by row
+    pub frame_buffer: Vec<u8>,
+}
+
+impl PPU {
+    pub fn new() -> Self {
+        PPU {
+            control: 0,
+            mask: 0,
+            status: 0,
+            oam_address: 0,
+            oam: [0; OAM_SIZE],
+            vram: [0; VRAM_SIZE],
+            palette: [0; PALETTE_SIZE],
+            vram_address: 0,
+            temporary_address: 0,
+            fine_x_scroll: 0,
+            write_toggle: false,
+            read_buffer: 0,
+            open_bus: 0,
+            frame_buffer: vec![0; FRAME_WIDTH * FRAME_HEIGHT],
+        }
+    }
+}
+
+impl Default for PPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::cartridge::mapper::Mapper;
+use crate::ppu::control_bit::ControlBit;
+use crate::ppu::ppu_memory::{PALETTE_START, PPU_ADDRESS_MASK};
+use crate::ppu::ppu_model::PPU;
+use crate::ppu::status_bit::StatusBit;
+
+pub const PPUCTRL: u16 = 0;
+pub const PPUMASK: u16 = 1;
+pub const PPUSTATUS: u16 = 2;
+pub const OAMADDR: u16 = 3;
+pub const OAMDATA: u16 = 4;
+pub const PPUSCROLL: u16 = 5;
+pub const PPUADDR: u16 = 6;
+pub const PPUDATA: u16 = 7;
+
+const STATUS_OPEN_BUS_MASK: u8 = 0b0001_1111;
+// Fields of t and v, high to low: fine Y (3 bits), nametable (2), coarse Y (5),
+// coarse X (5)
+const COARSE_X_MASK: u16 = 0x001F;
+const COARSE_Y_MASK: u16 = 0x03E0;
+const NAMETABLE_MASK: u16 = 0x0C00;
+const FINE_Y_MASK: u16 = 0x7000;
+
+impl PPU {
+    /// Handles a CPU read of $2000-$2007 (mirrored through $3FFF).
+    pub fn read_register(&mut self, address: u16, cartridge: &mut dyn Mapper) -> u8 {
+        let value = match address & 7 {
+            PPUSTATUS => {
+                let status =
+                    (self.status & !STATUS_OPEN_BUS_MASK) | (self.open_bus & STATUS_OPEN_BUS_MASK);
+                self.status &= !(1 << StatusBit::VerticalBlank as u8);
+                self.write_toggle = false;
+                status
+            }
+            OAMDATA => self.oam[self.oam_address as usize],
+            PPUDATA => self.read_data(cartridge),
+            _ => self.open_bus,
+        };
+        self.open_bus = value;
+        value
+    }
+
+    /// Handles a CPU write of $2000-$2007 (mirrored through $3FFF).
+    pub fn write_register(&mut self, address: u16, value: u8, cartridge: &mut dyn Mapper) {
+        self.open_bus = value;
+        match address & 7 {
+            PPUCTRL => {
+                self.control = value;
+                self.temporary_address =
+                    (self.temporary_address & !NAMETABLE_MASK) | ((value as u16 & 0b11) << 10);
+            }
+            PPUMASK => self.mask = value,
+            OAMADDR => self.oam_address = value,
+            OAMDATA => {
+                self.oam[self.oam_address as usize] = value;
+                self.oam_address = self.oam_address.wrapping_add(1);
+            }
+            PPUSCROLL => {
+                match self.write_toggle {
+                    false => {
+                        self.temporary_address =
+                            (self.temporary_address & !COARSE_X_MASK) | (value as u16 >> 3);
+                        self.fine_x_scroll = value & 0b111;
+                    }
+                    true => {
+                        self.temporary_address = (self.temporary_address
+                            & !(COARSE_Y_MASK | FINE_Y_MASK))
+                            | ((value as u16 & 0b111) << 12)
+                            | ((value as u16 & 0b1111_1000) << 2);
+                    }
+                }
+                self.write_toggle = !self.write_toggle;
+            }
+            PPUADDR => {
+                match self.write_toggle {
+                    // Only six bits fit; the top bit of t is cleared
+                    false => {
+                        self.temporary_address =
+                            (self.temporary_address & 0x00FF) | ((value as u16 & 0x3F) << 8);
+                    }
+                    true => {
+                        self.temporary_address = (self.temporary_address & 0xFF00) | value as u16;
+                        self.vram_address = self.temporary_address;
+                    }
+                }
+                self.write_toggle = !self.write_toggle;
+            }
+            PPUDATA => {
+                self.write_memory(self.vram_address, value, cartridge);
+                self.increment_vram_address();
+            }
+            _ => {}
+        }
+    }
+
+    // Reads below the palette return the previous read's byte. Palette reads are
+    // immediate, but still refill the buffer with the nametable byte underneath.
+    fn read_data(&mut self, cartridge: &mut dyn Mapper) -> u8 {
+        let address = self.vram_address & PPU_ADDRESS_MASK;
+        let value = match address >= PALETTE_START {
+            true => {
+                self.read_buffer = self.read_memory(address - 0x1000, cartridge);
+                self.read_memory(address, cartridge)
+            }
+            false => {
+                let buffered = self.read_buffer;
+                self.read_buffer = self.read_memory(address, cartridge);
+                buffered
+            }
+        };
+        self.increment_vram_address();
+        value
+    }
+
+    fn increment_vram_address(&mut self) {
+        let increment = match self.control & (1 << ControlBit::VramIncrement as u8) {
+            0 => 1,
+            _ => 32,
+        };
+        self.vram_address = self.vram_address.wrapping_add(increment) & 0x7FFF;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::test_rom::nrom;
+
+    #[test]
+    fn test_status_read_clears_vblank_and_write_toggle() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0);
+        ppu.status = 0b1100_0000;
+        ppu.write_register(PPUADDR, 0x21, &mut nrom);
+
+        let status = ppu.read_register(PPUSTATUS, &mut nrom);
+
+        // The low bits echo the last value written
+        assert_eq!(status, 0b1100_0001);
+        assert_eq!(ppu.status, 0b0100_0000);
+        assert!(!ppu.write_toggle);
+    }
+
+    #[test]
+    fn test_scroll_and_address_share_the_loopy_registers() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0);
+
+        ppu.write_register(PPUCTRL, 0b10, &mut nrom);
+        ppu.write_register(PPUSCROLL, 0b0111_1101, &mut nrom);
+        assert_eq!(ppu.fine_x_scroll, 0b101);
+        // Nametable 2, coarse X 15
+        assert_eq!(ppu.temporary_address, 0x080F);
+
+        ppu.write_register(PPUSCROLL, 0b0101_1110, &mut nrom);
+        // Fine Y 6, coarse Y 11
+        assert_eq!(ppu.temporary_address, 0x696F);
+        assert!(!ppu.write_toggle);
+
+        ppu.write_register(PPUADDR, 0xFF, &mut nrom);
+        assert_eq!(ppu.temporary_address, 0x3F6F);
+        ppu.write_register(PPUADDR, 0x12, &mut nrom);
+        assert_eq!(ppu.vram_address, 0x3F12);
+    }
+
+    #[test]
+    fn test_data_reads_are_buffered_except_palette() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0);
+        ppu.vram[0x305] = 0xAB;
+        ppu.palette[0x05] = 0x2C;
+
+        ppu.write_register(PPUADDR, 0x23, &mut nrom);
+        ppu.write_register(PPUADDR, 0x05, &mut nrom);
+        assert_eq!(ppu.read_register(PPUDATA, &mut nrom), 0x00);
+        assert_eq!(ppu.read_register(PPUDATA, &mut nrom), 0xAB);
+
+        // $3F05 sits over nametable byte $2F05, which mirrors to $2705 horizontally
+        ppu.vram[0x705] = 0x99;
+        ppu.write_register(PPUADDR, 0x3F, &mut nrom);
+        ppu.write_register(PPUADDR, 0x05, &mut nrom);
+        assert_eq!(ppu.read_register(PPUDATA, &mut nrom), 0x2C);
+        assert_eq!(ppu.read_buffer, 0x99);
+    }
+
+    #[test]
+    fn test_data_writes_increment_by_1_or_32() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0);
+        ppu.write_register(PPUADDR, 0x20, &mut nrom);
+        ppu.write_register(PPUADDR, 0x00, &mut nrom);
+
+        ppu.write_register(PPUDATA, 0x01, &mut nrom);
+        ppu.write_register(PPUCTRL, 1 << ControlBit::VramIncrement as u8, &mut nrom);
+        ppu.write_register(PPUDATA, 0x02, &mut nrom);
+        ppu.write_register(PPUDATA, 0x03, &mut nrom);
+
+        assert_eq!(ppu.vram[0x000], 0x01);
+        assert_eq!(ppu.vram[0x001], 0x02);
+        assert_eq!(ppu.vram[0x021], 0x03);
+        assert_eq!(ppu.vram_address, 0x2041);
+    }
+
+    #[test]
+    fn test_oam_data_increments_address_on_write_only() {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(0);
+        ppu.write_register(OAMADDR, 0xFF, &mut nrom);
+
+        ppu.write_register(OAMDATA, 0x12, &mut nrom);
+        ppu.write_register(OAMDATA, 0x34, &mut nrom);
+
+        assert_eq!(ppu.oam[0xFF], 0x12);
+        assert_eq!(ppu.oam[0x00], 0x34);
+        ppu.write_register(OAMADDR, 0xFF, &mut nrom);
+        assert_eq!(ppu.read_register(OAMDATA, &mut nrom), 0x12);
+        assert_eq!(ppu.read_register(OAMDATA, &mut nrom), 0x12);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::cartridge::mapper::Mapper;
+use crate::ppu::control_bit::ControlBit;
+use crate::ppu::mask_bit::MaskBit;
+use crate::ppu::ppu_model::{FRAME_HEIGHT, FRAME_WIDTH, PPU};
+
+const NAMETABLE_BASE: u16 = 0x2000;
+const ATTRIBUTE_TABLE_OFFSET: u16 = 0x03C0;
+const PATTERN_TABLE_SIZE: u16 = 0x1000;
+// The fetch pipeline reads one tile beyond the screen to cover fine X scrolling
+const TILES_PER_SCANLINE: usize = 33;
+
+const COARSE_X_MASK: u16 = 0x001F;
+const COARSE_Y_MASK: u16 = 0x03E0;
+const NAMETABLE_X_BIT: u16 = 0x0400;
+const NAMETABLE_Y_BIT: u16 = 0x0800;
+const FINE_Y_MASK: u16 = 0x7000;
+const HORIZONTAL_BITS: u16 = NAMETABLE_X_BIT | COARSE_X_MASK;
+const VERTICAL_BITS: u16 = FINE_Y_MASK | NAMETABLE_Y_BIT | COARSE_Y_MASK;
+const LAST_COARSE_Y_ROW: u16 = 29;
+const GREYSCALE_MASK: u8 = 0x30;
+
+impl PPU {
+    fn mask_enabled(&self, bit: MaskBit) -> bool {
+        self.mask & (1 << bit as u8) != 0
+    }
+
+    fn rendering_enabled(&self) -> bool {
+        self.mask_enabled(MaskBit::ShowBackground) || self.mask_enabled(MaskBit::ShowSprites)
+    }
+
+    /// Renders all 240 visible scanlines into `frame_buffer`, starting from the
+    /// scroll position in t like the pre-render line does.
+    pub fn render_frame(&mut self, cartridge: &mut dyn Mapper) {
+        if self.rendering_enabled() {
+            self.vram_address =
+                (self.vram_address & !VERTICAL_BITS) | (self.temporary_address & VERTICAL_BITS);
+        }
+        for scanline in 0..FRAME_HEIGHT {
+            self.render_scanline(scanline, cartridge);
+        }
+    }
+
+    /// Renders the background of one visible scanline and advances v as the
+    /// hardware does: coarse X once per tile, then Y at the end of the line.
+    pub fn render_scanline(&mut self, scanline: usize, cartridge: &mut dyn Mapper) {
+        let row = scanline * FRAME_WIDTH;
+        let backdrop = self.color(0);
+        if !self.rendering_enabled() {
+            self.frame_buffer[row..row + FRAME_WIDTH].fill(backdrop);
+            return;
+        }
+        self.vram_address =
+            (self.vram_address & !HORIZONTAL_BITS) | (self.temporary_address & HORIZONTAL_BITS);
+
+        let show_background = self.mask_enabled(MaskBit::ShowBackground);
+        let show_left = self.mask_enabled(MaskBit::ShowBackgroundLeft);
+        for tile in 0..TILES_PER_SCANLINE {
+            let (pattern_low, pattern_high, palette_number) = self.fetch_tile(cartridge);
+            for bit in 0..8 {
+                let x = (tile * 8 + bit) as isize - self.fine_x_scroll as isize;
+                if !(0..FRAME_WIDTH as isize).contains(&x) {
+                    continue;
+                }
+                let pixel =
+                    ((pattern_low >> (7 - bit)) & 1) | (((pattern_high >> (7 - bit)) & 1) << 1);
+                let visible = show_background && (show_left || x >= 8);
+                self.frame_buffer[row + x as usize] = match (visible, pixel) {
+                    (true, 1..=3) => self.color(palette_number * 4 + pixel),
+                    _ => backdrop,
+                };
+            }
+            self.increment_coarse_x();
+        }
+        self.increment_y();
+    }
+
+    // Nametable, attribute and both pattern bytes for the tile under v
+    fn fetch_tile(&mut self, cartridge: &mut dyn Mapper) -> (u8, u8, u8) {
+        let v = self.vram_address;
+        let tile = self.read_memory(NAMETABLE_BASE | (v & 0x0FFF), cartridge);
+        let attribute_address = NAMETABLE_BASE
+            | ATTRIBUTE_TABLE_OFFSET
+            | (v & (NAMETABLE_X_BIT | NAMETABLE_Y_BIT))
+            | ((v >> 4) & 0x38)
+            | ((v >> 2) & 0x07);
+        let attribute = self.read_memory(attribute_address, cartridge);
+        // Each attribute byte covers 4x4 tiles, two bits per 2x2 quadrant
+        let shift = ((v >> 4) & 0b100) | (v & 0b10);
+        let palette_number = (attribute >> shift) & 0b11;
+
+        let pattern_table = match self.control & (1 << ControlBit::BackgroundPatternTable as u8) {
+            0 => 0,
+            _ => PATTERN_TABLE_SIZE,
+        };
+        let fine_y = (v & FINE_Y_MASK) >> 12;
+        let pattern_address = pattern_table + tile as u16 * 16 + fine_y;
+        let pattern_low = self.read_memory(pattern_address, cartridge);
+        let pattern_high = self.read_memory(pattern_address + 8, cartridge);
+        (pattern_low, pattern_high, palette_number)
+    }
+
+    fn color(&self, palette_index: u8) -> u8 {
+        let color = self.palette[palette_index as usize] & 0x3F;
+        match self.mask_enabled(MaskBit::Greyscale) {
+            true => color & GREYSCALE_MASK,
+            false => color,
+        }
+    }
+
+    // Wraps into the horizontally adjacent nametable after column 31
+    fn increment_coarse_x(&mut self) {
+        match self.vram_address & COARSE_X_MASK {
+            COARSE_X_MASK => {
+                self.vram_address &= !COARSE_X_MASK;
+                self.vram_address ^= NAMETABLE_X_BIT;
+            }
+            _ => self.vram_address += 1,
+        }
+    }
+
+    // Fine Y first; row 29 wraps into the vertically adjacent nametable, while
+    // rows 30 and 31 (the attribute table) wrap without switching
+    fn increment_y(&mut self) {
+        if self.vram_address & FINE_Y_MASK != FINE_Y_MASK {
+            self.vram_address += 0x1000;
+            return;
+        }
+        self.vram_address &= !FINE_Y_MASK;
+        let coarse_y = (self.vram_address & COARSE_Y_MASK) >> 5;
+        let coarse_y = match coarse_y {
+            LAST_COARSE_Y_ROW => {
+                self.vram_address ^= NAMETABLE_Y_BIT;
+                0
+            }
+            31 => 0,
+            _ => coarse_y + 1,
+        };
+        self.vram_address = (self.vram_address & !COARSE_Y_MASK) | (coarse_y << 5);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridge::nrom::Nrom;
+    use crate::cartridge::test_rom::nrom;
+    use crate::ppu::ppu_registers::PPUSCROLL;
+
+    const BACKDROP: u8 = 0x0F;
+    const SHOW_BACKGROUND: u8 =
+        (1 << MaskBit::ShowBackground as u8) | (1 << MaskBit::ShowBackgroundLeft as u8);
+
+    // Tile 1 is 3 3 1 1 2 2 0 0 on row 0 and blank below; palette 2 is $11-$13
+    fn create_test_ppu(flags_6: u8) -> (PPU, Nrom) {
+        let mut ppu = PPU::new();
+        let mut nrom = nrom(flags_6);
+        nrom.ppu_write(0x0010, 0b1111_0000);
+        nrom.ppu_write(0x0018, 0b1100_1100);
+        ppu.palette[0] = BACKDROP;
+        ppu.palette[9..12].copy_from_slice(&[0x11, 0x12, 0x13]);
+        ppu.mask = SHOW_BACKGROUND;
+        (ppu, nrom)
+    }
+
+    fn pixels(ppu: &PPU, scanline: usize, x: usize, count: usize) -> &[u8] {
+        let start = scanline * FRAME_WIDTH + x;
+        &ppu.frame_buffer[start..start + count]
+    }
+
+    #[test]
+    fn test_tile_uses_attribute_palette() {
+        let (mut ppu, mut nrom) = create_test_ppu(0);
+        ppu.vram[0] = 1;
+        ppu.vram[0x3C0] = 0b10;
+
+        ppu.render_frame(&mut nrom);
+
+        assert_eq!(
+            pixels(&ppu, 0, 0, 8),
+            [0x13, 0x13, 0x11, 0x11, 0x12, 0x12, BACKDROP, BACKDROP]
+        );
+        // Row 1 of the tile is blank, and so is the tile to the right
+        assert_eq!(pixels(&ppu, 1, 0, 8), [BACKDROP; 8]);
+        assert_eq!(pixels(&ppu, 0, 8, 8), [BACKDROP; 8]);
+    }
+
+    #[test]
+    fn test_fine_scroll_and_coarse_y_rows() {
+        let (mut ppu, mut nrom) = create_test_ppu(0);
+        // Row 2, column 1: the bottom-left quadrant of the first attribute byte
+        ppu.vram[0x41] = 1;
+        ppu.vram[0x3C0] = 0b10 << 4;
+        ppu.write_register(PPUSCROLL, 10, &mut nrom);
+        ppu.write_register(PPUSCROLL, 3, &mut nrom);
+
+        ppu.render_frame(&mut nrom);
+
+        // Tile row 2 starts at pixel row 16; scrolled up 3 and left 10, the
+        // screen starts two pixels into the tile
+        assert_eq!(
+            pixels(&ppu, 13, 0, 6),
+            [0x11, 0x11, 0x12, 0x12, BACKDROP, BACKDROP]
+        );
+        assert_eq!(pixels(&ppu, 12, 0, 6), [BACKDROP; 6]);
+    }
+
+    #[test]
+    fn test_coarse_x_wraps_into_mirrored_nametable() {
+        // Vertical mirroring: the nametable at $2400 lives in the second KiB of VRAM
+        let (mut ppu, mut nrom) = create_test_ppu(0b0000_0001);
+        ppu.vram[0x400] = 1;
+        ppu.palette[1..4].copy_from_slice(&[0x21, 0x22, 0x23]);
+        ppu.write_register(PPUSCROLL, 8, &mut nrom);
+        ppu.write_register(PPUSCROLL, 0, &mut nrom);
+
+        ppu.render_frame(&mut nrom);
+
+        assert_eq!(
+            pixels(&ppu, 0, 248, 8),
+            [0x23, 0x23, 0x21, 0x21, 0x22, 0x22, BACKDROP, BACKDROP]
+        );
+    }
+
+    #[test]
+    fn test_disabled_and_clipped_background_show_backdrop() {
+        let (mut ppu, mut nrom) = create_test_ppu(0);
+        ppu.vram[0] = 1;
+        ppu.mask = 1 << MaskBit::ShowBackground as u8;
+
+        ppu.render_frame(&mut nrom);
+        assert_eq!(pixels(&ppu, 0, 0, 8), [BACKDROP; 8]);
+
+        ppu.mask = 0;
+        ppu.vram_address = 0x1234;
+        ppu.render_frame(&mut nrom);
+        assert!(ppu.frame_buffer.iter().all(|pixel| *pixel == BACKDROP));
+        assert_eq!(ppu.vram_address, 0x1234);
+    }
+}
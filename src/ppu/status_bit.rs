@@ -0,0 +1,8 @@
+/// Bits of PPUSTATUS ($2002); the low five bits are open bus.
+#[derive(Debug)]
+#[repr(u8)]
+pub enum StatusBit {
+    SpriteOverflow = 5,
+    SpriteZeroHit = 6,
+    VerticalBlank = 7,
+}
@@ -0,0 +1,64 @@
+use nes_pcfim::cpu::cpu_model::CPU;
+use nes_pcfim::cpu::memory::{Memory, MEMORY_SIZE};
+use nes_pcfim::cpu::trace::trace;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Defaults for an image assembled with `disable_decimal = 1`, since the NES CPU has
+// no BCD (see tests/fixtures/README.md). Other builds move the success trap, so both
+// addresses can be overridden with hex environment variables.
+const DEFAULT_START_ADDRESS: u16 = 0x0400;
+const DEFAULT_SUCCESS_ADDRESS: u16 = 0x336D;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn address_from_env(name: &str, default: u16) -> u16 {
+    match env::var(name) {
+        Ok(value) => u16::from_str_radix(value.trim_start_matches('$'), 16)
+            .unwrap_or_else(|_| panic!("{} must be a hex address, got `{}`", name, value)),
+        Err(_) => default,
+    }
+}
+
+fn load_image(image: &[u8], start_address: u16) -> CPU {
+    assert!(
+        image.len() <= MEMORY_SIZE,
+        "functional test image is larger than 64 KiB"
+    );
+    let mut memory = Memory::new();
+    memory.memory[..image.len()].copy_from_slice(image);
+    let mut cpu = CPU::with_bus(Box::new(memory));
+    cpu.program_counter = start_address;
+    cpu
+}
+
+#[test]
+#[ignore = "needs tests/fixtures/6502_functional_test.bin, see tests/fixtures/README.md"]
+fn test_klaus_functional() {
+    let image = fs::read(fixture("6502_functional_test.bin"))
+        .unwrap_or_else(|error| panic!("tests/fixtures/6502_functional_test.bin: {}", error));
+    let start_address = address_from_env("KLAUS_START_ADDRESS", DEFAULT_START_ADDRESS);
+    let success_address = address_from_env("KLAUS_SUCCESS_ADDRESS", DEFAULT_SUCCESS_ADDRESS);
+
+    let mut cpu = load_image(&image, start_address);
+    let trap_address = cpu
+        .run_until_trap()
+        .unwrap_or_else(|error| panic!("CPU error before trapping: {}\n{}", error, trace(&cpu)));
+
+    // The test number at $0200 identifies which block failed in the listing
+    assert_eq!(
+        trap_address,
+        success_address,
+        "trapped at ${:04X} instead of ${:04X} (test case {:#04x})\n{}",
+        trap_address,
+        success_address,
+        cpu.bus.peek(0x0200).unwrap(),
+        trace(&cpu)
+    );
+}
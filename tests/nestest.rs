@@ -0,0 +1,82 @@
+use nes_pcfim::cartridge::cartridge_model::Cartridge;
+use nes_pcfim::cartridge::mapper::create_mapper;
+use nes_pcfim::cpu::cpu_model::CPU;
+use nes_pcfim::cpu::memory_map::MemoryMap;
+use nes_pcfim::cpu::trace::trace;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Automation mode skips the menu and runs every test straight from $C000
+const AUTOMATION_START: u16 = 0xC000;
+const POWER_UP_STATUS: u8 = 0x24;
+const POWER_UP_CYCLES: u64 = 7;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn load_nestest(rom: &[u8]) -> CPU {
+    let cartridge = Cartridge::from_bytes(rom).expect("nestest.nes is not a valid iNES file");
+    let mapper = create_mapper(cartridge).expect("nestest.nes should be NROM");
+    let mut cpu = CPU::with_bus(Box::new(MemoryMap::new(mapper)));
+    cpu.program_counter = AUTOMATION_START;
+    cpu.status = POWER_UP_STATUS;
+    cpu.cycles = POWER_UP_CYCLES;
+    cpu
+}
+
+// Compares everything up to the PPU column, plus the CPU cycle count when the log has one.
+// Older logs put PPU dots in CYC and a scanline in SL, so those only compare the CPU state.
+fn lines_match(expected: &str, actual: &str) -> bool {
+    let state = |line: &str| {
+        line.split(" PPU:")
+            .next()
+            .and_then(|state| state.split(" CYC:").next())
+            .unwrap_or(line)
+            .trim_end()
+            .to_string()
+    };
+    let cycles = |line: &str| {
+        line.split(" CYC:")
+            .nth(1)
+            .map(|cycles| cycles.trim().to_string())
+    };
+    state(expected) == state(actual)
+        && (!expected.contains(" PPU:") || cycles(expected) == cycles(actual))
+}
+
+#[test]
+#[ignore = "needs tests/fixtures/nestest.nes and nestest.log, see tests/fixtures/README.md"]
+fn test_nestest_golden_log() {
+    let rom = fs::read(fixture("nestest.nes"))
+        .unwrap_or_else(|error| panic!("tests/fixtures/nestest.nes: {}", error));
+    let log = fs::read_to_string(fixture("nestest.log"))
+        .unwrap_or_else(|error| panic!("tests/fixtures/nestest.log: {}", error));
+
+    let mut cpu = load_nestest(&rom);
+    for (index, expected) in log.lines().enumerate() {
+        let actual = trace(&cpu);
+        assert!(
+            lines_match(expected, &actual),
+            "first mismatch at nestest.log line {}:\nexpected: {}\n  actual: {}",
+            index + 1,
+            expected,
+            actual
+        );
+        if let Err(error) = cpu.step() {
+            panic!(
+                "CPU error at nestest.log line {}: {}\n{}",
+                index + 1,
+                error,
+                expected
+            );
+        }
+    }
+
+    // nestest leaves its official and unofficial failure codes in $02 and $03
+    assert_eq!(cpu.bus.read(0x02), 0x00, "official opcode tests failed");
+    assert_eq!(cpu.bus.read(0x03), 0x00, "unofficial opcode tests failed");
+}
@@ -0,0 +1,258 @@
+use nes_pcfim::cpu::bus::Bus;
+use nes_pcfim::cpu::cpu_model::CPU;
+use nes_pcfim::cpu::memory::Memory;
+use nes_pcfim::cpu::operation_codes::{OperationName, OPERATION_TABLE};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+// Cap the report so a broken handler does not print thousands of cases
+const MAX_REPORTED_FAILURES: usize = 50;
+const STRICT_BUS_VARIABLE: &str = "SINGLE_STEP_STRICT_BUS";
+
+fn fixture_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("single_step")
+}
+
+fn field(state: &Value, name: &str) -> u64 {
+    state[name]
+        .as_u64()
+        .unwrap_or_else(|| panic!("missing or invalid field `{}`", name))
+}
+
+fn ram(state: &Value) -> Vec<(u16, u8)> {
+    state["ram"]
+        .as_array()
+        .expect("missing `ram` array")
+        .iter()
+        .map(|cell| {
+            (
+                cell[0].as_u64().unwrap() as u16,
+                cell[1].as_u64().unwrap() as u8,
+            )
+        })
+        .collect()
+}
+
+// Address, value and "read" or "write", as in the suite's `cycles` arrays
+type BusAccess = (u16, u8, &'static str);
+
+// Records every access so the bus activity can be diffed cycle by cycle
+struct LoggingBus {
+    memory: Memory,
+    log: Rc<RefCell<Vec<BusAccess>>>,
+}
+
+impl Bus for LoggingBus {
+    fn read(&mut self, address: u16) -> u8 {
+        let value = self.memory.read(address);
+        self.log.borrow_mut().push((address, value, "read"));
+        value
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.log.borrow_mut().push((address, value, "write"));
+        self.memory.write(address, value);
+    }
+
+    fn peek(&self, address: u16) -> Option<u8> {
+        self.memory.peek(address)
+    }
+}
+
+fn load_state(state: &Value) -> (CPU, Rc<RefCell<Vec<BusAccess>>>) {
+    let mut memory = Memory::new();
+    for (address, value) in ram(state) {
+        memory.memory[address as usize] = value;
+    }
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut cpu = CPU::with_bus(Box::new(LoggingBus {
+        memory,
+        log: Rc::clone(&log),
+    }));
+    cpu.program_counter = field(state, "pc") as u16;
+    cpu.stack_pointer = field(state, "s") as u8;
+    cpu.register_a = field(state, "a") as u8;
+    cpu.register_x = field(state, "x") as u8;
+    cpu.register_y = field(state, "y") as u8;
+    cpu.status = field(state, "p") as u8;
+    (cpu, log)
+}
+
+// Every difference between the CPU and the expected final state, one line each
+fn compare_state(cpu: &CPU, expected: &Value) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let registers = [
+        ("pc", cpu.program_counter as u64),
+        ("s", cpu.stack_pointer as u64),
+        ("a", cpu.register_a as u64),
+        ("x", cpu.register_x as u64),
+        ("y", cpu.register_y as u64),
+        ("p", cpu.status as u64),
+    ];
+    for (name, actual) in registers {
+        let wanted = field(expected, name);
+        if actual != wanted {
+            mismatches.push(format!(
+                "{}: expected {:#04x}, got {:#04x}",
+                name, wanted, actual
+            ));
+        }
+    }
+    for (address, wanted) in ram(expected) {
+        let actual = cpu.bus.peek(address).unwrap();
+        if actual != wanted {
+            mismatches.push(format!(
+                "ram[${:04X}]: expected {:#04x}, got {:#04x}",
+                address, wanted, actual
+            ));
+        }
+    }
+    mismatches
+}
+
+fn format_access(access: Option<(u16, u8, &str)>) -> String {
+    match access {
+        Some((address, value, kind)) => format!("{} ${:04X} = {:#04x}", kind, address, value),
+        None => "nothing".to_string(),
+    }
+}
+
+// One line per cycle whose bus access differs from the expected one
+fn compare_bus_log(log: &[BusAccess], expected: &[Value]) -> Vec<String> {
+    let expected: Vec<(u16, u8, &str)> = expected
+        .iter()
+        .map(|cycle| {
+            (
+                cycle[0].as_u64().unwrap() as u16,
+                cycle[1].as_u64().unwrap() as u8,
+                cycle[2].as_str().unwrap(),
+            )
+        })
+        .collect();
+    (0..log.len().max(expected.len()))
+        .filter_map(|index| {
+            let wanted = expected.get(index).copied();
+            let actual = log.get(index).copied();
+            (wanted != actual).then(|| {
+                format!(
+                    "bus cycle {}: expected {}, got {}",
+                    index,
+                    format_access(wanted),
+                    format_access(actual)
+                )
+            })
+        })
+        .collect()
+}
+
+// State mismatches (registers, RAM and cycle count) and bus mismatches, kept apart
+// because the CPU does not model the 6502's dummy reads yet
+fn run_case(case: &Value) -> (Vec<String>, Vec<String>) {
+    let (mut cpu, log) = load_state(&case["initial"]);
+    let result = match cpu.step() {
+        Ok(result) => result,
+        Err(error) => return (vec![format!("step failed: {}", error)], Vec::new()),
+    };
+
+    let mut mismatches = compare_state(&cpu, &case["final"]);
+    let bus_cycles = case["cycles"].as_array().expect("missing `cycles` array");
+    if result.cycles as usize != bus_cycles.len() {
+        mismatches.push(format!(
+            "cycles: expected {}, got {}",
+            bus_cycles.len(),
+            result.cycles
+        ));
+    }
+    let bus_mismatches = compare_bus_log(&log.borrow(), bus_cycles);
+    (mismatches, bus_mismatches)
+}
+
+fn report(path: &Path, case: &Value, mismatches: &[String]) -> String {
+    format!(
+        "{} `{}`:\n    {}",
+        path.file_name().unwrap().to_string_lossy(),
+        case["name"].as_str().unwrap_or("?"),
+        mismatches.join("\n    ")
+    )
+}
+
+#[test]
+#[ignore = "needs tests/fixtures/single_step/*.json, see tests/fixtures/README.md"]
+fn test_single_step_suite() {
+    let entries = fs::read_dir(fixture_directory())
+        .unwrap_or_else(|error| panic!("tests/fixtures/single_step: {}", error));
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+    paths.sort();
+
+    let mut failures = Vec::new();
+    let mut failed_cases = 0;
+    let mut bus_failures = Vec::new();
+    let mut bus_failed_cases = 0;
+    let mut case_count = 0;
+    for path in paths {
+        // Files are named after the opcode, e.g. `a9.json`. Unstable opcodes we do not
+        // implement and JAM, which locks the bus, are skipped.
+        let entry = path
+            .file_stem()
+            .and_then(|stem| u8::from_str_radix(&stem.to_string_lossy(), 16).ok())
+            .and_then(|code| OPERATION_TABLE[code as usize]);
+        match entry {
+            Some(entry) if entry.operation_name != OperationName::Jam => {}
+            _ => continue,
+        }
+
+        let text = fs::read_to_string(&path).unwrap();
+        let cases: Vec<Value> = serde_json::from_str(&text)
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+        for case in &cases {
+            case_count += 1;
+            let (mismatches, bus_mismatches) = run_case(case);
+            if !mismatches.is_empty() {
+                failed_cases += 1;
+                if failures.len() < MAX_REPORTED_FAILURES {
+                    failures.push(report(&path, case, &mismatches));
+                }
+            }
+            if !bus_mismatches.is_empty() {
+                bus_failed_cases += 1;
+                if bus_failures.len() < MAX_REPORTED_FAILURES {
+                    bus_failures.push(report(&path, case, &bus_mismatches));
+                }
+            }
+        }
+    }
+
+    // Bus activity only fails the run on request until dummy reads are modelled
+    if bus_failed_cases > 0 {
+        let summary = format!(
+            "{} of {} single step cases differ in bus activity:\n{}",
+            bus_failed_cases,
+            case_count,
+            bus_failures.join("\n")
+        );
+        assert!(env::var_os(STRICT_BUS_VARIABLE).is_none(), "{}", summary);
+        eprintln!("{}\nset {} to fail on these", summary, STRICT_BUS_VARIABLE);
+    }
+
+    assert!(
+        failed_cases == 0,
+        "{} of {} single step cases failed:\n{}",
+        failed_cases,
+        case_count,
+        failures.join("\n")
+    );
+}